@@ -0,0 +1,227 @@
+//! Pattern containment and pattern-avoiding permutations.
+
+/// Finds an occurrence of `pattern` in `perm`, optionally requiring `pattern[m]` to land on position `p` for `forced = (p, m)`.
+/// Positions are assigned left to right on an explicit stack, pruning as soon as the relative order differs.
+fn has_occurrence(perm: &[usize], pattern: &[usize], forced: Option<(usize, usize)>) -> bool {
+    let (n, k) = (perm.len(), pattern.len());
+    if k == 0 {
+        return true;
+    }
+    if k > n {
+        return false;
+    }
+    let mut positions = vec![0; k];
+    // Index into `pattern` being matched, and the first position to try for it
+    let mut a = 0;
+    let mut next = 0;
+    loop {
+        let mut lo = next;
+        let mut hi = n - (k - a);
+        if let Some((p, m)) = forced {
+            if a < m {
+                // Leave room before `p` for the entries between `a` and `m`
+                match p.checked_sub(m - a) {
+                    Some(last) => hi = hi.min(last),
+                    None => lo = hi + 1,
+                }
+            } else if a == m {
+                (lo, hi) = (lo.max(p), hi.min(p));
+            } else {
+                lo = lo.max(p + 1);
+            }
+        }
+        let found = (lo..=hi)
+            .find(|&j| (0..a).all(|b| (perm[positions[b]] < perm[j]) == (pattern[b] < pattern[a])));
+        match found {
+            Some(j) => {
+                positions[a] = j;
+                a += 1;
+                if a == k {
+                    return true;
+                }
+                next = j + 1;
+            }
+            None if a == 0 => return false,
+            None => {
+                a -= 1;
+                next = positions[a] + 1;
+            }
+        }
+    }
+}
+
+/// `true` if some subsequence of `perm` is in the same relative order as `pattern`.
+/// Entries of each slice must be distinct. Has $O(n^k)$ worst-case time complexity for a pattern of length `k`.
+pub fn contains_pattern(perm: &[usize], pattern: &[usize]) -> bool {
+    has_occurrence(perm, pattern, None)
+}
+
+/// Implements ``Iterator`` over the permutations of `0..n` that avoid all of the given patterns.
+///
+/// Walks the generating tree where the children of a permutation of `0..m` insert `m` at each position.
+/// Deleting the maximum never creates an occurrence, so the avoiders of length `m + 1` are exactly the
+/// avoiding children of avoiders of length `m`, and only occurrences through the inserted maximum need checking.
+/// The tree is traversed with an explicit stack rather than recursion.
+pub struct PatternAvoiders {
+    n: usize,
+    patterns: Vec<Vec<usize>>,
+    perm: Vec<usize>,
+    /// Next insertion position to try for value `m` at level `m`.
+    site: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl PatternAvoiders {
+    /// Each pattern must be a non-empty permutation of `0..k`, and n must be greater than 0!
+    pub fn of<P: AsRef<[usize]>>(n: usize, patterns: &[P]) -> PatternAvoiders {
+        assert!(n > 0);
+        let patterns: Vec<Vec<usize>> = patterns
+            .iter()
+            .map(|pattern| pattern.as_ref().to_vec())
+            .collect();
+        for pattern in &patterns {
+            let mut sorted = pattern.clone();
+            sorted.sort_unstable();
+            assert!(!pattern.is_empty() && sorted.iter().enumerate().all(|(ii, &i)| ii == i));
+        }
+        PatternAvoiders {
+            n,
+            patterns,
+            perm: Vec::with_capacity(n),
+            site: vec![0; n],
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_finished {
+            return None;
+        }
+        if self.is_initiated {
+            self.pop();
+        }
+        self.is_initiated = true;
+        if self.search() {
+            Some(&self.perm)
+        } else {
+            self.is_finished = true;
+            None
+        }
+    }
+
+    /// Calls `f` with every remaining permutation without allocating.
+    pub fn for_each_perm<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(perm) = self.next_ref() {
+            f(perm);
+        }
+    }
+
+    /// Removes the largest value, undoing the last insertion.
+    fn pop(&mut self) {
+        let m = self.perm.len() - 1;
+        self.perm.remove(self.site[m] - 1);
+    }
+
+    /// Descends the generating tree to the next avoider of length n. Returns `false` if there is none.
+    fn search(&mut self) -> bool {
+        while self.perm.len() < self.n {
+            let m = self.perm.len();
+            let mut is_inserted = false;
+            while self.site[m] <= m {
+                let p = self.site[m];
+                self.site[m] += 1;
+                self.perm.insert(p, m);
+                let creates_pattern = self.patterns.iter().any(|pattern| {
+                    let top = pattern
+                        .iter()
+                        .position(|&i| i == pattern.len() - 1)
+                        .unwrap();
+                    has_occurrence(&self.perm, pattern, Some((p, top)))
+                });
+                if !creates_pattern {
+                    is_inserted = true;
+                    break;
+                }
+                self.perm.remove(p);
+            }
+            if is_inserted {
+                if m + 1 < self.n {
+                    self.site[m + 1] = 0;
+                }
+            } else if m == 0 {
+                return false;
+            } else {
+                self.pop();
+            }
+        }
+        true
+    }
+}
+
+impl Iterator for PatternAvoiders {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|perm| perm.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{binomial, contains_pattern, PatternAvoiders, Permutations};
+    use std::collections::HashSet;
+
+    /// Checks ``contains_pattern()`` on small examples
+    #[test]
+    fn pattern_containment() {
+        assert!(contains_pattern(&[3, 0, 4, 1, 2], &[1, 2, 0]));
+        assert!(!contains_pattern(&[4, 3, 0, 2, 1], &[0, 1, 2]));
+        assert!(contains_pattern(&[4, 3, 0, 2, 1], &[2, 1, 0]));
+        assert!(contains_pattern(&[2, 0, 1], &[]));
+        assert!(!contains_pattern(&[0, 1], &[1, 0, 2]));
+        assert!(contains_pattern(&[10, 30, 20], &[0, 2, 1]));
+    }
+
+    /// Checks against filtering all permutations, and Catalan numbers for single patterns of length 3
+    #[test]
+    fn matches_filtered_permutations() {
+        let pattern_sets: [&[&[usize]]; 4] = [
+            &[&[1, 2, 0]],
+            &[&[0, 1, 2]],
+            &[&[1, 2, 0], &[2, 0, 1]],
+            &[&[1, 0, 3, 2]],
+        ];
+        for patterns in pattern_sets {
+            for n in 1..=7 {
+                let expected: HashSet<Vec<usize>> = Permutations::of(n)
+                    .filter(|perm| {
+                        patterns
+                            .iter()
+                            .all(|pattern| !contains_pattern(perm, pattern))
+                    })
+                    .collect();
+                let perms: Vec<Vec<usize>> = PatternAvoiders::of(n, patterns).collect();
+                assert_eq!(perms.len(), expected.len());
+                assert_eq!(perms.into_iter().collect::<HashSet<_>>(), expected);
+            }
+        }
+        for n in 1..=10 {
+            let catalan = binomial(2 * n, n).unwrap() / (n as u128 + 1);
+            assert_eq!(
+                PatternAvoiders::of(n, &[[1, 2, 0]]).count() as u128,
+                catalan
+            );
+            assert_eq!(
+                PatternAvoiders::of(n, &[[0, 1, 2]]).count() as u128,
+                catalan
+            );
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! k-subsets of `0..n` in revolving-door order.
+
+use crate::binomial;
+
+/// Implements ``Iterator`` over the `k`-subsets of `0..n` in revolving-door order, as ascending ``Vec``s.
+///
+/// Consecutive subsets differ by exchanging one element for another. Uses Knuth's Algorithm R,
+/// so each step has $O(1)$ amortized time complexity.
+pub struct Combinations {
+    n: usize,
+    k: usize,
+    /// `comb[1..=k]` is the current subset in ascending order, and `comb[k + 1] == n` is a sentinel.
+    comb: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl Combinations {
+    /// n must be greater than 0 and k must not exceed n!
+    pub fn of(n: usize, k: usize) -> Combinations {
+        assert!(n > 0);
+        assert!(k <= n);
+        let mut comb: Vec<usize> = (0..k + 2).map(|j| j.saturating_sub(1)).collect();
+        comb[k + 1] = n;
+        Combinations {
+            n,
+            k,
+            comb,
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    pub fn get_k(&self) -> usize {
+        self.k
+    }
+
+    /// Number of k-subsets, or `None` if it does not fit in ``u128``.
+    pub fn total(&self) -> Option<u128> {
+        binomial(self.n, self.k)
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_initiated {
+            self.next_change()?;
+        }
+        self.is_initiated = true;
+        Some(&self.comb[1..=self.k])
+    }
+
+    /// Steps to the next subset and returns the `(added, removed)` pair of elements.
+    /// The first subset counts as already visited, so the first call performs the first exchange.
+    pub fn next_change(&mut self) -> Option<(usize, usize)> {
+        self.is_initiated = true;
+        if self.is_finished {
+            return None;
+        }
+        let change = self.step();
+        self.is_finished = change.is_none();
+        change
+    }
+
+    /// Calls `f` with every remaining subset without allocating.
+    pub fn for_each_comb<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(comb) = self.next_ref() {
+            f(comb);
+        }
+    }
+
+    /// Algorithm R, steps R3 to R5.
+    fn step(&mut self) -> Option<(usize, usize)> {
+        let (k, c) = (self.k, &mut self.comb);
+        if k == 0 {
+            return None;
+        }
+        // Easy case: move the smallest element
+        if k % 2 == 1 && c[1] + 1 < c[2] {
+            c[1] += 1;
+            return Some((c[1], c[1] - 1));
+        }
+        if k % 2 == 0 && c[1] > 0 {
+            c[1] -= 1;
+            return Some((c[1], c[1] + 1));
+        }
+        let mut j = 2;
+        let mut try_decrease = k % 2 == 1;
+        while j <= k {
+            if try_decrease {
+                // Here c[j] == c[j - 1] + 1
+                if c[j] >= j {
+                    let removed = c[j];
+                    c[j] = c[j - 1];
+                    c[j - 1] = j - 2;
+                    return Some((j - 2, removed));
+                }
+                j += 1;
+                if j > k {
+                    break;
+                }
+            }
+            // Here c[j - 1] == j - 2; try to increase c[j]
+            if c[j] + 1 < c[j + 1] {
+                let removed = c[j - 1];
+                c[j - 1] = c[j];
+                c[j] += 1;
+                return Some((c[j], removed));
+            }
+            j += 1;
+            try_decrease = true;
+        }
+        None
+    }
+}
+
+impl Iterator for Combinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|comb| comb.to_vec())
+    }
+}
+
+/// Index of an ascending `comb` in the revolving-door order of ``Combinations.of(n, comb.len())``, for any n.
+/// Has $O(k^2)$ time complexity. `comb` must be strictly ascending!
+pub fn combination_rank(comb: &[usize]) -> u128 {
+    // The order lists subsets without c before the reversed order of those containing it,
+    // which gives rank(c_1..c_i) = C(c_i + 1, i) - 1 - rank(c_1..c_(i-1))
+    let mut rank: u128 = 0;
+    for (i, &c) in comb.iter().enumerate() {
+        rank = binomial(c + 1, i + 1).unwrap() - 1 - rank;
+    }
+    rank
+}
+
+/// The `rank`-th subset generated by ``Combinations.of(n, k)``, ascending. Has $O(nk)$ time complexity.
+/// k must not exceed n and `rank` must be less than $\binom{n}{k}$!
+pub fn combination_unrank(n: usize, k: usize, rank: u128) -> Vec<usize> {
+    assert!(k <= n && rank < binomial(n, k).unwrap());
+    let mut comb = vec![0; k];
+    let mut rank = rank;
+    let mut limit = n;
+    // Undo ``combination_rank()`` from the largest element down: c_i is the largest c with C(c, i) <= rank
+    for i in (1..=k).rev() {
+        let c = (0..limit)
+            .rev()
+            .find(|&c| binomial(c, i).unwrap() <= rank)
+            .unwrap();
+        comb[i - 1] = c;
+        rank = binomial(c + 1, i).unwrap() - 1 - rank;
+        limit = c;
+    }
+    comb
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{combination_rank, combination_unrank, Combinations};
+
+    /// Revolving-door order from its recursive definition
+    fn revolving_door(n: usize, k: usize) -> Vec<Vec<usize>> {
+        if k == 0 {
+            return vec![vec![]];
+        }
+        if k == n {
+            return vec![(0..n).collect()];
+        }
+        let mut order = revolving_door(n - 1, k);
+        for mut comb in revolving_door(n - 1, k - 1).into_iter().rev() {
+            comb.push(n - 1);
+            order.push(comb);
+        }
+        order
+    }
+
+    /// Checks the order, the reported exchanges, and ranking
+    #[test]
+    fn revolving_door_order() {
+        for n in 1..=8 {
+            for k in 0..=n {
+                let expected = revolving_door(n, k);
+                let combs = Combinations::of(n, k);
+                assert_eq!(combs.total(), Some(expected.len() as u128));
+                assert_eq!(combs.collect::<Vec<_>>(), expected);
+                let mut changes = Combinations::of(n, k);
+                for w in expected.windows(2) {
+                    let (added, removed) = changes.next_change().unwrap();
+                    assert!(w[0].contains(&removed) && !w[0].contains(&added));
+                    assert!(w[1].contains(&added) && !w[1].contains(&removed));
+                }
+                assert_eq!(changes.next_change(), None);
+                for (r, comb) in expected.iter().enumerate() {
+                    assert_eq!(combination_rank(comb), r as u128);
+                    assert_eq!(&combination_unrank(n, k, r as u128), comb);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Exact, overflow-checked counting functions. Each returns `None` if the result does not fit in ``u128``.
+
+pub(crate) fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Number of permutations of `0..n`, i.e. $n!$.
+pub fn factorial(n: usize) -> Option<u128> {
+    falling_factorial(n, n)
+}
+
+/// Number of ordered selections of `k` out of `n` items, i.e. $n! / (n - k)!$.
+pub fn falling_factorial(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    (n - k + 1..=n).try_fold(1u128, |result, i| result.checked_mul(i as u128))
+}
+
+/// Number of derangements of `0..n`, i.e. permutations without fixed points. Also written as $!n$.
+pub fn subfactorial(n: usize) -> Option<u128> {
+    if n == 0 {
+        return Some(1);
+    }
+    // !n = (n - 1) (!(n - 1) + !(n - 2)), starting from !0 = 1 and !1 = 0
+    let (mut prev, mut current): (u128, u128) = (1, 0);
+    for i in 2..=n as u128 {
+        (prev, current) = (current, (i - 1).checked_mul(prev.checked_add(current)?)?);
+    }
+    Some(current)
+}
+
+/// Number of `k`-subsets of an `n`-set.
+pub fn binomial(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    // After step i, result is C(n - k + i, i), so it never exceeds the final value
+    for i in 1..=k as u128 {
+        let g = gcd(result, i);
+        result = (result / g).checked_mul((n as u128 - k as u128 + i) / (i / g))?;
+    }
+    Some(result)
+}
+
+/// Number of distinct arrangements of a multiset where value `i` appears `multiplicities[i]` times.
+pub fn multinomial(multiplicities: &[usize]) -> Option<u128> {
+    let mut result: u128 = 1;
+    let mut n = 0;
+    for &m in multiplicities {
+        n += m;
+        result = result.checked_mul(binomial(n, m)?)?;
+    }
+    Some(result)
+}
+
+/// Number of partitions of an `n`-set into `k` non-empty blocks, the Stirling number of the second kind.
+pub fn stirling2(n: usize, k: usize) -> Option<u128> {
+    // Row i of the triangle, from S(i, j) = j S(i - 1, j) + S(i - 1, j - 1)
+    let mut row: Vec<u128> = vec![0; k + 1];
+    row[0] = 1;
+    for i in 1..=n {
+        for j in (1..=k.min(i)).rev() {
+            row[j] = (j as u128).checked_mul(row[j])?.checked_add(row[j - 1])?;
+        }
+        row[0] = 0;
+    }
+    Some(row[k])
+}
+
+/// Number of partitions of an `n`-set into non-empty blocks.
+pub fn bell(n: usize) -> Option<u128> {
+    // Bell triangle: each row starts with the last entry of the previous one
+    let mut row: Vec<u128> = vec![1];
+    for _ in 0..n {
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(*row.last().unwrap());
+        for &entry in &row {
+            next.push(next.last().unwrap().checked_add(entry)?);
+        }
+        row = next;
+    }
+    Some(row[0])
+}
+
+/// Number of permutations whose cycle lengths are the given partition, $n! / \prod_k k^{m_k} m_k!$
+/// where $m_k$ is the number of cycles of length `k`. Cycle lengths must be positive!
+pub fn cycle_type_count(cycle_type: &[usize]) -> Option<u128> {
+    let mut sorted = cycle_type.to_vec();
+    sorted.sort_unstable();
+    let mut result: u128 = 1;
+    let mut remaining: usize = sorted.iter().sum();
+    // Pick the elements of each group of equal-length cycles, then fill the cycles one by one,
+    // each starting at its smallest element, so that every factor divides the final result
+    for group in sorted.chunk_by(|a, b| a == b) {
+        let size = group.len() * group[0];
+        result = result.checked_mul(binomial(remaining, size)?)?;
+        for j in 0..group.len() {
+            result =
+                result.checked_mul(falling_factorial(size - j * group[0] - 1, group[0] - 1)?)?;
+        }
+        remaining -= size;
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bell, binomial, cycle_type_count, factorial, falling_factorial, multinomial, stirling2,
+        subfactorial,
+    };
+
+    /// Checks a few hand-computed values
+    #[test]
+    fn small_values() {
+        assert_eq!(factorial(0), Some(1));
+        assert_eq!(factorial(5), Some(120));
+        assert_eq!(falling_factorial(5, 2), Some(20));
+        assert_eq!(falling_factorial(5, 6), Some(0));
+        assert_eq!(subfactorial(0), Some(1));
+        assert_eq!(subfactorial(1), Some(0));
+        assert_eq!(subfactorial(5), Some(44));
+        assert_eq!(cycle_type_count(&[2, 2, 1]), Some(15));
+        assert_eq!(cycle_type_count(&[3, 1]), Some(8));
+        assert_eq!(cycle_type_count(&[]), Some(1));
+        assert_eq!(stirling2(5, 2), Some(15));
+        assert_eq!(stirling2(0, 0), Some(1));
+        assert_eq!(stirling2(3, 0), Some(0));
+        assert_eq!(bell(0), Some(1));
+        assert_eq!(bell(5), Some(52));
+        assert_eq!(binomial(5, 2), Some(10));
+        assert_eq!(binomial(5, 7), Some(0));
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(multinomial(&[2, 1, 1]), Some(12));
+        assert_eq!(multinomial(&[]), Some(1));
+    }
+
+    /// Checks the values right at the ``u128`` boundary
+    #[test]
+    fn overflow_is_detected() {
+        assert_eq!(
+            binomial(130, 65),
+            Some(95067625827960698145584333020095113100)
+        );
+        assert_eq!(binomial(132, 66), None);
+        assert_eq!(
+            multinomial(&[1; 34]),
+            Some(295232799039604140847618609643520000000)
+        );
+        assert_eq!(multinomial(&[1; 35]), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Permutations with a prescribed cycle type.
+
+use crate::cycle_type_count;
+
+/// Implements ``Iterator`` over the permutations of `0..n` whose cycle lengths form the given partition of n.
+///
+/// Each permutation is built in its canonical cycle form: every cycle starts at the smallest element not used yet,
+/// and continues with any unused elements. Both the cycle lengths and the elements are chosen on an explicit
+/// backtracking stack, so each permutation of the cycle type is produced exactly once and nothing is filtered.
+pub struct CycleTypePermutations {
+    n: usize,
+    perm: Vec<usize>,
+    /// Cycle lengths in descending order.
+    cycle_type: Vec<usize>,
+    /// Distinct cycle lengths in descending order, and how many cycles of each length are still unplaced.
+    lengths: Vec<usize>,
+    unplaced: Vec<usize>,
+    /// Elements in canonical cycle form, one slot per element.
+    slots: Vec<usize>,
+    /// Slots still to fill in the current cycle after each slot.
+    left: Vec<usize>,
+    /// Next choice to try at each slot: a length index at cycle starts, an element otherwise.
+    choice: Vec<usize>,
+    used: Vec<bool>,
+    /// Number of slots currently filled.
+    depth: usize,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl CycleTypePermutations {
+    /// Cycle lengths may be in any order. They must be positive and their sum n must be greater than 0!
+    pub fn of(cycle_type: &[usize]) -> CycleTypePermutations {
+        assert!(cycle_type.iter().all(|&length| length > 0));
+        let n = cycle_type.iter().sum();
+        assert!(n > 0);
+        let mut sorted = cycle_type.to_vec();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        let mut lengths = sorted.clone();
+        lengths.dedup();
+        let unplaced = lengths
+            .iter()
+            .map(|length| sorted.iter().filter(|&l| l == length).count())
+            .collect();
+        CycleTypePermutations {
+            n,
+            perm: vec![0; n],
+            cycle_type: sorted,
+            lengths,
+            unplaced,
+            slots: vec![0; n],
+            left: vec![0; n],
+            choice: vec![0; n],
+            used: vec![false; n],
+            depth: 0,
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of permutations with this cycle type, $n! / \prod_k k^{m_k} m_k!$, or `None` if it does not fit in ``u128``.
+    pub fn total(&self) -> Option<u128> {
+        cycle_type_count(&self.cycle_type)
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_finished {
+            return None;
+        }
+        if self.is_initiated {
+            self.pop();
+        }
+        self.is_initiated = true;
+        if !self.search() {
+            self.is_finished = true;
+            return None;
+        }
+        // Convert canonical cycle form to one-line form
+        let mut start = 0;
+        for s in 0..self.n {
+            let next = if self.left[s] == 0 { start } else { s + 1 };
+            self.perm[self.slots[s]] = self.slots[next];
+            if self.left[s] == 0 {
+                start = s + 1;
+            }
+        }
+        Some(&self.perm)
+    }
+
+    /// Calls `f` with every remaining permutation without allocating.
+    pub fn for_each_perm<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(perm) = self.next_ref() {
+            f(perm);
+        }
+    }
+
+    fn is_cycle_start(&self, s: usize) -> bool {
+        s == 0 || self.left[s - 1] == 0
+    }
+
+    /// Empties the last filled slot.
+    fn pop(&mut self) {
+        self.depth -= 1;
+        let s = self.depth;
+        self.used[self.slots[s]] = false;
+        if self.is_cycle_start(s) {
+            self.unplaced[self.choice[s] - 1] += 1;
+        }
+    }
+
+    /// Fills the remaining slots with the next valid choices. Returns `false` if there are none.
+    fn search(&mut self) -> bool {
+        while self.depth < self.n {
+            let s = self.depth;
+            let filled = if self.is_cycle_start(s) {
+                let leader = self.used.iter().position(|&used| !used).unwrap();
+                let next = (self.choice[s]..self.lengths.len()).find(|&li| self.unplaced[li] > 0);
+                next.map(|li| {
+                    self.unplaced[li] -= 1;
+                    self.choice[s] = li + 1;
+                    (leader, self.lengths[li] - 1)
+                })
+            } else {
+                let next = (self.choice[s]..self.n).find(|&i| !self.used[i]);
+                next.map(|i| {
+                    self.choice[s] = i + 1;
+                    (i, self.left[s - 1] - 1)
+                })
+            };
+            match filled {
+                Some((i, left)) => {
+                    self.slots[s] = i;
+                    self.used[i] = true;
+                    self.left[s] = left;
+                    self.depth += 1;
+                    if self.depth < self.n {
+                        self.choice[self.depth] = 0;
+                    }
+                }
+                None if s == 0 => return false,
+                None => self.pop(),
+            }
+        }
+        true
+    }
+}
+
+impl Iterator for CycleTypePermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|perm| perm.to_vec())
+    }
+}
+
+/// Iterates over the involutions of `0..n`, i.e. permutations with only 1- and 2-cycles, by number of 2-cycles.
+/// n must be greater than 0!
+pub fn involutions(n: usize) -> impl Iterator<Item = Vec<usize>> {
+    (0..=n / 2).flat_map(move |pairs| {
+        let cycle_type: Vec<usize> = std::iter::repeat_n(2, pairs)
+            .chain(std::iter::repeat_n(1, n - 2 * pairs))
+            .collect();
+        CycleTypePermutations::of(&cycle_type)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{involutions, CycleTypePermutations, Permutation, Permutations};
+    use std::collections::HashSet;
+
+    /// Checks against filtering all permutations by cycle type, and the counting formula
+    #[test]
+    fn matches_filtered_permutations() {
+        for cycle_type in [
+            &[1][..],
+            &[3, 3],
+            &[2, 1, 1, 2],
+            &[4, 2, 1],
+            &[3, 1, 3],
+            &[1; 5],
+            &[6],
+        ] {
+            let n = cycle_type.iter().sum();
+            let mut sorted = cycle_type.to_vec();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            let expected: HashSet<Vec<usize>> = Permutations::of(n)
+                .filter(|perm| Permutation::new(perm.clone()).unwrap().cycle_type() == sorted)
+                .collect();
+            let perms = CycleTypePermutations::of(cycle_type);
+            assert_eq!(perms.total(), Some(expected.len() as u128));
+            let perms: Vec<Vec<usize>> = perms.collect();
+            assert_eq!(perms.len(), expected.len());
+            assert_eq!(perms.into_iter().collect::<HashSet<_>>(), expected);
+        }
+    }
+
+    /// Checks involutions against their definition
+    #[test]
+    fn involutions_are_self_inverse() {
+        for n in 1..=7 {
+            let expected = Permutations::of(n)
+                .filter(|perm| perm.iter().enumerate().all(|(ii, &i)| perm[i] == ii))
+                .count();
+            assert_eq!(involutions(n).count(), expected);
+        }
+    }
+}
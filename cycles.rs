@@ -0,0 +1,282 @@
+//! Cycle decomposition and cycle notation for ``Permutation``.
+
+use crate::Permutation;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error for malformed cycle notation, or cycles that are not disjoint on `0..n`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CycleError {
+    /// Byte offset of a character that does not belong at that point of the notation.
+    UnexpectedChar(usize),
+    /// The notation ends inside a cycle.
+    UnclosedCycle,
+    /// Byte offset of a number that does not fit in ``usize``.
+    InvalidNumber(usize),
+    /// Element that is not less than n.
+    OutOfRange(usize),
+    /// Element that appears more than once, i.e. overlapping cycles.
+    Repeated(usize),
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CycleError::UnexpectedChar(offset) => {
+                write!(f, "unexpected character at offset {}", offset)
+            }
+            CycleError::UnclosedCycle => write!(f, "unclosed cycle"),
+            CycleError::InvalidNumber(offset) => write!(f, "invalid number at offset {}", offset),
+            CycleError::OutOfRange(element) => write!(f, "element {} is out of range", element),
+            CycleError::Repeated(element) => {
+                write!(f, "element {} appears in more than one place", element)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+impl Permutation {
+    /// Disjoint cycles including fixed points, each starting at its smallest element, ordered by that element.
+    /// Cycle `[a, b, c]` means `a` maps to `b`, `b` to `c` and `c` to `a`. Has $O(n)$ time complexity.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let perm = self.as_slice();
+        let mut visited = vec![false; perm.len()];
+        let mut cycles = Vec::new();
+        for start in 0..perm.len() {
+            if visited[start] {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                cycle.push(i);
+                i = perm[i];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// Builds a permutation of `0..n` from disjoint cycles; elements not mentioned are fixed points.
+    pub fn from_cycles<C: AsRef<[usize]>>(
+        n: usize,
+        cycles: &[C],
+    ) -> Result<Permutation, CycleError> {
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut seen = vec![false; n];
+        for cycle in cycles {
+            let cycle = cycle.as_ref();
+            for (ii, &i) in cycle.iter().enumerate() {
+                if i >= n {
+                    return Err(CycleError::OutOfRange(i));
+                }
+                if seen[i] {
+                    return Err(CycleError::Repeated(i));
+                }
+                seen[i] = true;
+                perm[i] = cycle[(ii + 1) % cycle.len()];
+            }
+        }
+        Ok(Permutation::new(perm).unwrap())
+    }
+
+    /// Parses cycle notation like `(0 2 1)(3 4)` for a permutation of `0..n`.
+    /// Elements may be separated by whitespace or commas; `()` and the empty string denote the identity.
+    pub fn parse_cycles(s: &str, n: usize) -> Result<Permutation, CycleError> {
+        Permutation::from_cycles(n, &parse_cycle_list(s)?)
+    }
+
+    /// Lengths of the cycles in descending order, a partition of n.
+    pub fn cycle_type(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.cycles().iter().map(Vec::len).collect();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        lengths
+    }
+}
+
+/// Splits cycle notation into its cycles without checking that they are disjoint.
+fn parse_cycle_list(s: &str) -> Result<Vec<Vec<usize>>, CycleError> {
+    let mut cycles = Vec::new();
+    let mut current: Option<Vec<usize>> = None;
+    // Offset of a comma that is still waiting for the next number
+    let mut comma = None;
+    let mut chars = s.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        match (c, &mut current) {
+            (c, _) if c.is_whitespace() => {}
+            ('(', None) => current = Some(Vec::new()),
+            (')', Some(_)) => {
+                if let Some(comma) = comma {
+                    return Err(CycleError::UnexpectedChar(comma));
+                }
+                cycles.push(current.take().unwrap());
+            }
+            (',', Some(cycle)) if !cycle.is_empty() && comma.is_none() => comma = Some(offset),
+            ('0'..='9', Some(cycle)) => {
+                comma = None;
+                let mut end = offset + 1;
+                while let Some(&(next, '0'..='9')) = chars.peek() {
+                    end = next + 1;
+                    chars.next();
+                }
+                cycle.push(
+                    s[offset..end]
+                        .parse()
+                        .map_err(|_| CycleError::InvalidNumber(offset))?,
+                );
+            }
+            _ => return Err(CycleError::UnexpectedChar(offset)),
+        }
+    }
+    if current.is_some() {
+        return Err(CycleError::UnclosedCycle);
+    }
+    Ok(cycles)
+}
+
+/// Largest n that ``from_str()`` infers, which bounds its allocation. Use ``parse_cycles()`` for larger n.
+const MAX_INFERRED_N: usize = 1 << 24;
+
+/// Parses cycle notation, taking n to be one more than the largest element. See ``parse_cycles()``.
+/// Elements from ``MAX_INFERRED_N`` on are reported as ``CycleError.OutOfRange``.
+impl FromStr for Permutation {
+    type Err = CycleError;
+
+    fn from_str(s: &str) -> Result<Permutation, CycleError> {
+        let cycles = parse_cycle_list(s)?;
+        let n = match cycles.iter().flatten().max() {
+            Some(&i) if i >= MAX_INFERRED_N => return Err(CycleError::OutOfRange(i)),
+            Some(&i) => i + 1,
+            None => 0,
+        };
+        Permutation::from_cycles(n, &cycles)
+    }
+}
+
+/// Writes standard cycle notation like `(0 2 1)(3 4)`, omitting fixed points; the identity is `()`.
+impl fmt::Display for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut is_identity = true;
+        for cycle in self.cycles().iter().filter(|cycle| cycle.len() > 1) {
+            is_identity = false;
+            write!(f, "(")?;
+            for (ii, i) in cycle.iter().enumerate() {
+                if ii > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", i)?;
+            }
+            write!(f, ")")?;
+        }
+        if is_identity {
+            write!(f, "()")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CycleError, Permutation, Permutations};
+
+    /// Checks decomposition, printing and parsing on a fixed example
+    #[test]
+    fn cycle_notation() {
+        let perm = Permutation::new(vec![2, 0, 1, 4, 3, 5]).unwrap();
+        assert_eq!(perm.cycles(), [vec![0, 2, 1], vec![3, 4], vec![5]]);
+        assert_eq!(perm.cycle_type(), [3, 2, 1]);
+        assert_eq!(perm.to_string(), "(0 2 1)(3 4)");
+        assert_eq!(
+            Permutation::parse_cycles("(0 2 1)(3 4)", 6),
+            Ok(perm.clone())
+        );
+        assert_eq!(Permutation::parse_cycles(" ( 1, 0 2 ) (4,3) ", 6), Ok(perm));
+        assert_eq!(Permutation::identity(3).to_string(), "()");
+        assert_eq!("()".parse(), Ok(Permutation::identity(0)));
+        assert_eq!(
+            "(3 1)".parse::<Permutation>().unwrap().into_vec(),
+            [0, 3, 2, 1]
+        );
+    }
+
+    /// Checks error reporting for malformed notation and overlapping cycles
+    #[test]
+    fn malformed_cycle_notation() {
+        assert_eq!(
+            Permutation::parse_cycles("(0 1", 3),
+            Err(CycleError::UnclosedCycle)
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0 (1))", 3),
+            Err(CycleError::UnexpectedChar(3))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("0 1", 3),
+            Err(CycleError::UnexpectedChar(0))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0 x)", 3),
+            Err(CycleError::UnexpectedChar(3))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(,0)", 3),
+            Err(CycleError::UnexpectedChar(1))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0,,1)", 3),
+            Err(CycleError::UnexpectedChar(3))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0 1,)", 3),
+            Err(CycleError::UnexpectedChar(4))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0 1 ,)(2)", 3),
+            Err(CycleError::UnexpectedChar(5))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0, 1 ,2)", 3),
+            Permutation::parse_cycles("(0 1 2)", 3)
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0 3)", 3),
+            Err(CycleError::OutOfRange(3))
+        );
+        assert_eq!(
+            Permutation::parse_cycles("(0 1)(1 2)", 3),
+            Err(CycleError::Repeated(1))
+        );
+        assert_eq!(
+            "(0 99999999999999999999999)".parse::<Permutation>(),
+            Err(CycleError::InvalidNumber(3))
+        );
+        assert_eq!(
+            "(18446744073709551615)".parse::<Permutation>(),
+            Err(CycleError::OutOfRange(usize::MAX))
+        );
+        assert_eq!(
+            "(0 4000000000000)".parse::<Permutation>(),
+            Err(CycleError::OutOfRange(4000000000000))
+        );
+    }
+
+    /// Checks round trips through cycle notation for all permutations of 5
+    #[test]
+    fn round_trips() {
+        for perm in Permutations::of(5) {
+            let perm = Permutation::new(perm).unwrap();
+            assert_eq!(
+                Permutation::from_cycles(5, &perm.cycles()),
+                Ok(perm.clone())
+            );
+            assert_eq!(
+                Permutation::parse_cycles(&perm.to_string(), 5),
+                Ok(perm.clone())
+            );
+            assert_eq!(perm.cycle_type().iter().sum::<usize>(), 5);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Derangements, and more generally permutations avoiding prescribed positions.
+
+use crate::subfactorial;
+
+/// Implements ``Iterator`` over the derangements of `0..n`, i.e. permutations without fixed points, in lexicographic order.
+///
+/// Positions are filled left to right with an explicit backtracking stack instead of recursion,
+/// so arrangements with fixed points are pruned as soon as they appear rather than generated and filtered.
+pub struct Derangements {
+    n: usize,
+    perm: Vec<usize>,
+    /// `forbidden[i * n + v]` is `true` if position `i` must not hold value `v`.
+    forbidden: Vec<bool>,
+    used: Vec<bool>,
+    /// Smallest value still to be tried at each position of the stack.
+    candidate: Vec<usize>,
+    /// Number of positions currently filled.
+    depth: usize,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl Derangements {
+    /// n must be greater than 0!
+    pub fn of(n: usize) -> Derangements {
+        Derangements::with_forbidden(n, (0..n).map(|i| (i, i)))
+    }
+
+    /// Generalization to permutations of `0..n` where position `i` never holds value `v` for each `(i, v)` in `forbidden`.
+    /// n must be greater than 0 and all pairs must be within `0..n`!
+    pub fn with_forbidden<I: IntoIterator<Item = (usize, usize)>>(
+        n: usize,
+        forbidden: I,
+    ) -> Derangements {
+        assert!(n > 0);
+        let mut matrix = vec![false; n * n];
+        for (i, v) in forbidden {
+            assert!(i < n && v < n);
+            matrix[i * n + v] = true;
+        }
+        Derangements {
+            n,
+            perm: vec![0; n],
+            forbidden: matrix,
+            used: vec![false; n],
+            candidate: vec![0; n],
+            depth: 0,
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of derangements, the subfactorial of n, or `None` if it does not fit in ``u128``.
+    /// Also `None` for ``with_forbidden()`` unless exactly the fixed points are forbidden,
+    /// as counting other restrictions is a permanent computation that is not provided.
+    pub fn total(&self) -> Option<u128> {
+        let n = self.n;
+        let is_derangement = (0..n * n).all(|k| self.forbidden[k] == (k % (n + 1) == 0));
+        is_derangement.then(|| subfactorial(n)).flatten()
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_finished {
+            return None;
+        }
+        if self.is_initiated {
+            // Undo the last position so the search resumes after the previous arrangement
+            self.depth -= 1;
+            self.used[self.perm[self.depth]] = false;
+        }
+        self.is_initiated = true;
+        if self.search() {
+            Some(&self.perm)
+        } else {
+            self.is_finished = true;
+            None
+        }
+    }
+
+    /// Calls `f` with every remaining arrangement without allocating.
+    pub fn for_each_perm<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(perm) = self.next_ref() {
+            f(perm);
+        }
+    }
+
+    /// Extends the current partial arrangement to the next complete one. Returns `false` if there is none.
+    fn search(&mut self) -> bool {
+        let n = self.n;
+        while self.depth < n {
+            let i = self.depth;
+            let mut v = self.candidate[i];
+            while v < n && (self.used[v] || self.forbidden[i * n + v]) {
+                v += 1;
+            }
+            if v < n {
+                self.perm[i] = v;
+                self.used[v] = true;
+                self.candidate[i] = v + 1;
+                self.depth += 1;
+                if self.depth < n {
+                    self.candidate[self.depth] = 0;
+                }
+            } else if i == 0 {
+                return false;
+            } else {
+                self.depth -= 1;
+                self.used[self.perm[self.depth]] = false;
+            }
+        }
+        true
+    }
+}
+
+impl Iterator for Derangements {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|perm| perm.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{subfactorial, Derangements, LexPermutations};
+
+    /// Checks against filtering all permutations, and the subfactorial count
+    #[test]
+    fn matches_filtered_permutations() {
+        for n in 1..=7 {
+            let expected: Vec<Vec<usize>> = LexPermutations::of(n)
+                .filter(|perm| perm.iter().enumerate().all(|(ii, &i)| ii != i))
+                .collect();
+            assert_eq!(Derangements::of(n).collect::<Vec<_>>(), expected);
+            assert_eq!(subfactorial(n), Some(expected.len() as u128));
+            assert_eq!(Derangements::of(n).total(), subfactorial(n));
+        }
+    }
+
+    /// Checks the forbidden positions generalization
+    #[test]
+    fn forbidden_positions() {
+        let forbidden = [(0, 0), (0, 1), (2, 3), (3, 0), (3, 3)];
+        let expected: Vec<Vec<usize>> = LexPermutations::of(4)
+            .filter(|perm| forbidden.iter().all(|&(i, v)| perm[i] != v))
+            .collect();
+        assert_eq!(
+            Derangements::with_forbidden(4, forbidden).collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(Derangements::with_forbidden(4, forbidden).total(), None);
+        assert_eq!(
+            Derangements::with_forbidden(3, [(2, 2), (0, 0), (1, 1)]).total(),
+            Some(2)
+        );
+        assert_eq!(
+            Derangements::with_forbidden(3, [(0, 0), (1, 0), (2, 0)]).count(),
+            0
+        );
+    }
+}
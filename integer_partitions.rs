@@ -0,0 +1,234 @@
+//! Integer partitions and compositions of n.
+
+use crate::GraySubsets;
+
+/// Implements ``Iterator`` over the partitions of n into positive parts, each in descending order,
+/// in reverse lexicographic order from `[n]` to `[1, 1, ..., 1]`.
+///
+/// Each step decreases the rightmost part that can be decreased and refills the rest greedily, so it has
+/// $O(n)$ time complexity, and the bounds never cause partitions to be generated and then skipped.
+pub struct IntegerPartitions {
+    n: usize,
+    max_parts: usize,
+    parts: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl IntegerPartitions {
+    /// n must be greater than 0!
+    pub fn of(n: usize) -> IntegerPartitions {
+        IntegerPartitions::bounded(n, n, n)
+    }
+
+    /// Only partitions with at most `max_parts` parts, each at most `max_part`. n must be greater than 0!
+    pub fn bounded(n: usize, max_parts: usize, max_part: usize) -> IntegerPartitions {
+        assert!(n > 0);
+        let mut partitions = IntegerPartitions {
+            n,
+            max_parts,
+            parts: Vec::with_capacity(n),
+            is_initiated: false,
+            is_finished: !partitions_fit(n, max_part, max_parts),
+        };
+        if !partitions.is_finished {
+            partitions.fill(n, max_part.min(n));
+        }
+        partitions
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_initiated && !self.is_finished && !self.step() {
+            self.is_finished = true;
+        }
+        self.is_initiated = true;
+        if self.is_finished {
+            None
+        } else {
+            Some(&self.parts)
+        }
+    }
+
+    /// Calls `f` with every remaining partition without allocating.
+    pub fn for_each_partition<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(parts) = self.next_ref() {
+            f(parts);
+        }
+    }
+
+    /// Appends the largest parts not exceeding `largest` that sum to `total`.
+    fn fill(&mut self, mut total: usize, largest: usize) {
+        while total > 0 {
+            let part = largest.min(total);
+            self.parts.push(part);
+            total -= part;
+        }
+    }
+
+    /// Moves to the next partition. Returns `false` if there is none.
+    fn step(&mut self) -> bool {
+        let mut suffix = 0;
+        for j in (0..self.parts.len()).rev() {
+            suffix += self.parts[j];
+            let part = self.parts[j] - 1;
+            if part > 0 && partitions_fit(suffix - part, part, self.max_parts - j - 1) {
+                self.parts.truncate(j);
+                self.parts.push(part);
+                self.fill(suffix - part, part);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// `true` if `total` can be split into at most `count` parts of at most `largest`.
+fn partitions_fit(total: usize, largest: usize, count: usize) -> bool {
+    largest
+        .checked_mul(count)
+        .is_none_or(|capacity| total <= capacity)
+}
+
+impl Iterator for IntegerPartitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|parts| parts.to_vec())
+    }
+}
+
+/// Implements ``Iterator`` over the $2^{n - 1}$ compositions of n, i.e. ordered sequences of positive parts summing to n.
+///
+/// A composition is given by the set of cut points among `1..n`, which are walked in Gray code order with
+/// ``GraySubsets``, so each step splits one part in two or merges two adjacent parts, starting from `[n]`.
+pub struct Compositions {
+    n: usize,
+    /// Cut point `i` lies between units `i` and `i + 1`. `None` for n = 1, which has no cut points.
+    cuts: Option<GraySubsets>,
+    parts: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl Compositions {
+    /// n must be greater than 0!
+    pub fn of(n: usize) -> Compositions {
+        assert!(n > 0);
+        Compositions {
+            n,
+            cuts: (n > 1).then(|| GraySubsets::of(n - 1)),
+            parts: vec![n],
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_finished {
+            return None;
+        }
+        if !self.is_initiated {
+            self.is_initiated = true;
+            return Some(&self.parts);
+        }
+        let Some(cuts) = self.cuts.as_mut() else {
+            self.is_finished = true;
+            return None;
+        };
+        if cuts.next_flip().is_none() {
+            self.is_finished = true;
+            return None;
+        }
+        self.parts.clear();
+        let mut start = 0;
+        for i in 0..self.n - 1 {
+            if cuts.contains(i) {
+                self.parts.push(i + 1 - start);
+                start = i + 1;
+            }
+        }
+        self.parts.push(self.n - start);
+        Some(&self.parts)
+    }
+
+    /// Calls `f` with every remaining composition without allocating.
+    pub fn for_each_composition<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(parts) = self.next_ref() {
+            f(parts);
+        }
+    }
+}
+
+impl Iterator for Compositions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|parts| parts.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Compositions, IntegerPartitions, Permutation, Permutations};
+    use std::collections::HashSet;
+
+    /// Checks partitions against the cycle types of all permutations, and the bounds against filtering
+    #[test]
+    fn integer_partitions() {
+        for n in 1..=7 {
+            let cycle_types: HashSet<Vec<usize>> = Permutations::of(n)
+                .map(|perm| Permutation::new(perm).unwrap().cycle_type())
+                .collect();
+            let partitions: Vec<Vec<usize>> = IntegerPartitions::of(n).collect();
+            assert!(partitions.windows(2).all(|w| w[0] > w[1]));
+            assert_eq!(partitions.len(), cycle_types.len());
+            assert_eq!(
+                partitions.iter().cloned().collect::<HashSet<_>>(),
+                cycle_types
+            );
+            for max_parts in 0..=n + 1 {
+                for max_part in 0..=n + 1 {
+                    let expected: Vec<Vec<usize>> = partitions
+                        .iter()
+                        .filter(|parts| parts.len() <= max_parts && parts[0] <= max_part)
+                        .cloned()
+                        .collect();
+                    let bounded: Vec<Vec<usize>> =
+                        IntegerPartitions::bounded(n, max_parts, max_part).collect();
+                    assert_eq!(bounded, expected);
+                }
+            }
+        }
+        assert_eq!(IntegerPartitions::of(30).count(), 5604);
+    }
+
+    /// Checks that compositions are distinct, sum to n, and differ by one split or merge
+    #[test]
+    fn compositions() {
+        for n in 1..=10 {
+            let compositions: Vec<Vec<usize>> = Compositions::of(n).collect();
+            assert_eq!(compositions.len(), 1 << (n - 1));
+            assert_eq!(compositions[0], [n]);
+            assert!(compositions
+                .iter()
+                .all(|parts| parts.iter().sum::<usize>() == n && !parts.contains(&0)));
+            assert_eq!(
+                compositions.iter().collect::<HashSet<_>>().len(),
+                compositions.len()
+            );
+            assert!(compositions
+                .windows(2)
+                .all(|w| w[0].len().abs_diff(w[1].len()) == 1));
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Partial permutations, i.e. ordered selections of `k` out of `n` items.
+
+use crate::{falling_factorial, next_permutation};
+
+/// Implements ``Iterator`` over the k-permutations of `0..n` in lexicographic order.
+///
+/// The first `k` entries of an internal permutation of `0..n` form the arrangement; the remaining entries are
+/// kept ascending, so reversing them lets ``next_permutation()`` skip straight to the next prefix.
+/// Each step has $O(n)$ time complexity.
+pub struct KPermutations {
+    n: usize,
+    k: usize,
+    perm: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl KPermutations {
+    /// n must be greater than 0 and k must not exceed n!
+    pub fn of(n: usize, k: usize) -> KPermutations {
+        assert!(n > 0);
+        assert!(k <= n);
+        KPermutations {
+            n,
+            k,
+            perm: (0..n).collect(),
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    pub fn get_k(&self) -> usize {
+        self.k
+    }
+
+    /// Number of k-permutations, $n! / (n - k)!$, or `None` if it does not fit in ``u128``.
+    pub fn total(&self) -> Option<u128> {
+        falling_factorial(self.n, self.k)
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if !self.is_initiated {
+            self.is_initiated = true;
+            return Some(&self.perm[..self.k]);
+        }
+        if self.is_finished {
+            return None;
+        }
+        self.perm[self.k..].reverse();
+        if !next_permutation(&mut self.perm) {
+            self.is_finished = true;
+            return None;
+        }
+        Some(&self.perm[..self.k])
+    }
+
+    /// Calls `f` with every remaining k-permutation without allocating.
+    pub fn for_each_perm<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(perm) = self.next_ref() {
+            f(perm);
+        }
+    }
+}
+
+impl Iterator for KPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|perm| perm.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{KPermutations, LexPermutations};
+
+    /// Checks against deduplicated prefixes of full permutations
+    #[test]
+    fn matches_truncated_permutations() {
+        for n in 1..=6 {
+            for k in 0..=n {
+                let mut expected: Vec<Vec<usize>> = LexPermutations::of(n)
+                    .map(|perm| perm[..k].to_vec())
+                    .collect();
+                expected.dedup();
+                let perms = KPermutations::of(n, k);
+                assert_eq!(perms.total(), Some(expected.len() as u128));
+                assert_eq!(perms.collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Permutations in lexicographic order.
+
+use crate::next_permutation;
+
+/// Implements ``Iterator`` over permutations of `0..n` in lexicographic order.
+///
+/// Each step uses ``next_permutation()``, so it has $O(n)$ worst-case and $O(1)$ amortized time complexity.
+pub struct LexPermutations {
+    n: usize,
+    perm: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl LexPermutations {
+    /// n must be greater than 0!
+    pub fn of(n: usize) -> LexPermutations {
+        assert!(n > 0);
+        LexPermutations {
+            n,
+            perm: (0..n).collect(),
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if !self.is_initiated {
+            self.is_initiated = true;
+            return Some(&self.perm);
+        }
+        if self.is_finished || !next_permutation(&mut self.perm) {
+            self.is_finished = true;
+            return None;
+        }
+        Some(&self.perm)
+    }
+
+    /// Calls `f` with every remaining permutation without allocating.
+    pub fn for_each_perm<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(perm) = self.next_ref() {
+            f(perm);
+        }
+    }
+}
+
+impl Iterator for LexPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|perm| perm.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LexPermutations, Permutations};
+
+    /// Checks that the output is sorted and complete
+    #[test]
+    fn lexicographic_order() {
+        for n in 1..=6 {
+            let perms: Vec<Vec<usize>> = LexPermutations::of(n).collect();
+            let mut sjt: Vec<Vec<usize>> = Permutations::of(n).collect();
+            sjt.sort();
+            assert_eq!(perms, sjt);
+        }
+    }
+}
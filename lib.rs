@@ -2,25 +2,183 @@
 //!
 //! ``Permutations.of(n)`` function generates an iterator instance for permutations of `0..n`.
 //!
-//! ``Permutations.next()`` uses Steinhaus-Johnson-Trotter algorithm with Even's modification to generate the next permutation
-//! in $O(n)$ time.
+//! ``Permutations.next()`` uses Steinhaus-Johnson-Trotter algorithm with Even's modification to generate the next permutation.
+//! The transition itself is loopless, i.e. $O(1)$ in the worst case; returning the permutation as a ``Vec`` costs $O(n)$.
+//!
+//! ``Permutations.next_ref()`` and ``Permutations.for_each_perm()`` expose the internal buffer as ``&[usize]`` instead,
+//! so streaming over all permutations does not allocate.
+//!
+//! ``HeapPermutations.of(n)`` offers the same interface using Heap's algorithm.
+//!
+//! ``LexPermutations.of(n)`` generates permutations in lexicographic order, built on ``next_permutation()``
+//! which, like ``prev_permutation()``, works on slices of any ``Ord`` type.
+//!
+//! ``MultisetPermutations.of(multiplicities)`` generates each distinct arrangement of a multiset exactly once.
+//!
+//! ``KPermutations.of(n, k)`` generates the ordered selections of `k` out of `0..n`.
+//!
+//! ``rank()`` and ``unrank()`` convert between a permutation and its index in SJT order,
+//! ``lex_rank()`` and ``lex_unrank()`` do the same for lexicographic order.
+//!
+//! ``Permutations.from_perm(perm)`` and ``Permutations.from_rank(n, rank)`` resume the SJT order anywhere,
+//! and ``nth()`` jumps ahead through ranks instead of stepping.
+//!
+//! ``Permutations.split(n, parts)`` and ``Permutations.with_ranks(n, ranks)`` divide the SJT order into
+//! independent rank ranges. With the `rayon` feature, ``Permutations.par_of(n)`` is an ``IndexedParallelIterator``
+//! built on them.
+//!
+//! ``Permutations.exact()`` is an ``ExactSizeIterator`` when the remaining count fits in ``usize``,
+//! otherwise ``remaining_u128()`` reports it.
+//!
+//! ``Permutations`` is also a ``DoubleEndedIterator``, so ``rev()`` walks the SJT order backwards.
+//!
+//! ``Permutation`` is a validated permutation value with composition, inverse and powers.
+//! It converts to and from disjoint cycles, and prints and parses cycle notation like `(0 2 1)(3 4)`.
+//! Statistics such as sign, inversions, descents, major index and order are available on it, and
+//! ``Permutations.sign()`` tracks the sign of the SJT sequence for free.
+//!
+//! ``Derangements.of(n)`` generates the permutations without fixed points, and
+//! ``Derangements.with_forbidden(n, pairs)`` those avoiding arbitrary position-value pairs.
+//!
+//! ``CycleTypePermutations.of(cycle_type)`` generates the permutations with given cycle lengths, and
+//! ``involutions(n)`` chains those with only 1- and 2-cycles.
+//!
+//! With the `rand` feature, ``random_permutation()``, ``random_derangement()`` and ``random_rank()`` sample uniformly,
+//! and ``ShuffledPermutations.new(n, rng)`` visits all permutations once in a seeded pseudo-random order.
+//!
+//! ``PatternAvoiders.of(n, patterns)`` generates the permutations avoiding classical patterns through a generating tree,
+//! and ``contains_pattern()`` tests for a single pattern.
+//!
+//! ``Combinations.of(n, k)`` generates the k-subsets of `0..n` in revolving-door order, where each step exchanges one element.
+//!
+//! ``GraySubsets.of(n)`` generates all subsets of `0..n` in binary reflected Gray code order, one flip per step.
+//!
+//! ``SetPartitions.of(n)`` generates the partitions of `0..n` as restricted growth strings, moving one element per step,
+//! and ``SetPartitions.with_blocks(n, k)`` those with exactly `k` blocks.
+//!
+//! ``IntegerPartitions.of(n)`` and ``IntegerPartitions.bounded(n, max_parts, max_part)`` generate the partitions of n,
+//! e.g. to drive per-cycle-type work, and ``Compositions.of(n)`` its compositions.
+//!
+//! ``MixedRadix.of(radices)`` counts through mixed-radix digit vectors, and ``MixedRadix.gray(radices)`` does so
+//! in reflected Gray code order, one digit $\pm 1$ per step. ``PermutationProduct.of(ns)`` uses the latter to
+//! permute inside several groups at once, swapping two adjacent entries of one group per step.
 //!
 //! Each iterator is one-way. You need to construct a new one for iterating again.
 
+mod avoid;
+mod combinations;
+mod count;
+mod cycle_type;
+mod cycles;
+mod derange;
+mod heap;
+mod integer_partitions;
+mod kperm;
+mod lex;
+mod mixed_radix;
+mod multiset;
+#[cfg(feature = "rayon")]
+mod par;
+mod partitions;
+mod perm;
+#[cfg(feature = "rand")]
+mod random;
+mod rank;
+mod stats;
+mod subsets;
+
+pub use avoid::{contains_pattern, PatternAvoiders};
+pub use combinations::{combination_rank, combination_unrank, Combinations};
+pub use count::{
+    bell, binomial, cycle_type_count, factorial, falling_factorial, multinomial, stirling2,
+    subfactorial,
+};
+pub use cycle_type::{involutions, CycleTypePermutations};
+pub use cycles::CycleError;
+pub use derange::Derangements;
+pub use heap::HeapPermutations;
+pub use integer_partitions::{Compositions, IntegerPartitions};
+pub use kperm::KPermutations;
+pub use lex::LexPermutations;
+pub use mixed_radix::{MixedRadix, PermutationProduct};
+pub use multiset::MultisetPermutations;
+#[cfg(feature = "rayon")]
+pub use par::ParPermutations;
+pub use partitions::{rgs_to_blocks, SetPartitions};
+pub use perm::{NotAPermutation, Permutation};
+#[cfg(feature = "rand")]
+pub use random::{random_derangement, random_permutation, random_rank, ShuffledPermutations};
+pub use rank::{lex_rank, lex_unrank, rank, unrank};
+pub use subsets::GraySubsets;
+
+use std::ops::Range;
+
 /// Generates the inverse permutation. Has $O(n)$ time complexity.
-pub fn inverse_perm(perm: &Vec<usize>) -> Vec<usize> {
-    let mut rev_perm = perm.clone();
+pub fn inverse_perm(perm: &[usize]) -> Vec<usize> {
+    let mut rev_perm = perm.to_vec();
     for (ii, i) in perm.iter().enumerate() {
         rev_perm[*i] = ii;
     }
     rev_perm
 }
 
+/// Rearranges `items` into the lexicographically next permutation, like C++'s ``std::next_permutation``.
+/// If `items` is already the last permutation, sorts it ascending and returns `false`. Has $O(n)$ time complexity.
+pub fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    // Find the longest non-increasing suffix
+    let Some(i) = (1..items.len()).rev().find(|&i| items[i - 1] < items[i]) else {
+        items.reverse();
+        return false;
+    };
+    // Swap the pivot with the rightmost larger element, then make the suffix ascending
+    let j = (i..items.len())
+        .rev()
+        .find(|&j| items[i - 1] < items[j])
+        .unwrap();
+    items.swap(i - 1, j);
+    items[i..].reverse();
+    true
+}
+
+/// Rearranges `items` into the lexicographically previous permutation, like C++'s ``std::prev_permutation``.
+/// If `items` is already the first permutation, sorts it descending and returns `false`. Has $O(n)$ time complexity.
+pub fn prev_permutation<T: Ord>(items: &mut [T]) -> bool {
+    // Find the longest non-decreasing suffix
+    let Some(i) = (1..items.len()).rev().find(|&i| items[i - 1] > items[i]) else {
+        items.reverse();
+        return false;
+    };
+    // Swap the pivot with the rightmost smaller element, then make the suffix descending
+    let j = (i..items.len())
+        .rev()
+        .find(|&j| items[i - 1] > items[j])
+        .unwrap();
+    items.swap(i - 1, j);
+    items[i..].reverse();
+    true
+}
+
 /// Implements ``Iterator``.
+///
+/// Value `v` of the permutation moves exactly like digit `n - 1 - v` of a reflected mixed-radix
+/// Gray code, so the element to move is found through Ehrlich's focus pointers instead of a scan.
 pub struct Permutations {
     n: usize,
     perm: Vec<usize>,
-    direction: Vec<i8>,
+    /// Inverse of `perm`: `inv[v]` is the position of value `v`.
+    inv: Vec<usize>,
+    /// Gray code digit `t` counts the steps taken by value `n - 1 - t` in its current sweep.
+    digit: Vec<usize>,
+    /// `true` while digit `t` is increasing, i.e. value `n - 1 - t` is moving left.
+    rising: Vec<bool>,
+    /// Focus pointers; `focus[0]` is the digit to change on the next step.
+    focus: Vec<usize>,
+    /// Rank of `perm` and the exclusive end of the ranks to visit. `None` if $n!$ does not fit in ``u128``.
+    bounds: Option<(u128, u128)>,
+    /// Cursor for ``next_back()``, created on first use. See ``DoubleEndedIterator`` for how it works.
+    back: Option<Box<Permutations>>,
+    /// Parity of `perm`, which flips with every transposition.
+    is_odd: bool,
     is_initiated: bool,
     is_finished: bool,
 }
@@ -31,73 +189,387 @@ impl Permutations {
         assert!(n > 0);
         Permutations {
             n,
-            perm: vec![0; n],
-            direction: vec![0; n],
+            perm: (0..n).collect(),
+            inv: (0..n).collect(),
+            digit: vec![0; n - 1],
+            rising: vec![true; n - 1],
+            focus: (0..n).collect(),
+            bounds: factorial(n).map(|total| (0, total)),
+            back: None,
+            is_odd: false,
             is_initiated: false,
             is_finished: false,
         }
     }
 
+    /// Resumes the SJT order at `perm`, which the next call to ``next()`` returns. Has $O(n^2)$ time complexity.
+    /// `perm` must be a non-empty permutation of `0..n`!
+    pub fn from_perm(perm: &[usize]) -> Permutations {
+        let n = perm.len();
+        let mut perms = Permutations::of(n);
+        let mut seen = vec![false; n];
+        for (ii, &i) in perm.iter().enumerate() {
+            assert!(i < n && !seen[i]);
+            seen[i] = true;
+            perms.perm[ii] = i;
+            perms.inv[i] = ii;
+        }
+        if let Some((start, _)) = &mut perms.bounds {
+            *start = rank(perm);
+        }
+        // Same decomposition as `rank()`, but only the parity of the sub-permutation ranks is needed
+        let mut is_finished = vec![false; n - 1];
+        let mut rank_is_odd = false;
+        for v in 1..n {
+            let t = n - 1 - v;
+            let smaller_left = perm[..perms.inv[v]].iter().filter(|&&i| i < v).count();
+            let offset = if rank_is_odd {
+                smaller_left
+            } else {
+                v - smaller_left
+            };
+            // A digit that completed its sweep has already flipped its direction
+            is_finished[t] = offset == v;
+            perms.digit[t] = v - smaller_left;
+            perms.rising[t] = rank_is_odd == is_finished[t];
+            rank_is_odd = (rank_is_odd && v % 2 == 0) != (offset % 2 == 1);
+        }
+        // Every SJT step is a transposition, so the parity of the rank is the parity of `perm`
+        perms.is_odd = rank_is_odd;
+        // Each run of finished digits points past itself from its first digit
+        let mut t = 0;
+        while t < n - 1 {
+            let start = t;
+            while t < n - 1 && is_finished[t] {
+                t += 1;
+            }
+            if t == start {
+                t += 1;
+            } else {
+                perms.focus[start] = t;
+            }
+        }
+        perms
+    }
+
+    /// Resumes the SJT order at the permutation with the given rank, see ``unrank()``.
+    /// n must be greater than 0 and `rank` must be less than $n!$!
+    pub fn from_rank(n: usize, rank: u128) -> Permutations {
+        Permutations::from_perm(&unrank(n, rank))
+    }
+
+    /// Visits only the permutations whose SJT rank lies in `ranks`.
+    /// Disjoint ranges give independent iterators, e.g. for different threads.
+    /// n must be greater than 0 and at most 34, and `ranks` must be within `0..=n!`!
+    pub fn with_ranks(n: usize, ranks: Range<u128>) -> Permutations {
+        let total = factorial(n).unwrap();
+        assert!(ranks.start <= ranks.end && ranks.end <= total);
+        let mut perms = Permutations::from_rank(n, ranks.start.min(total - 1));
+        perms.bounds = Some((ranks.start, ranks.end));
+        perms
+    }
+
+    /// Splits the permutations of `0..n` into `parts` contiguous rank ranges of nearly equal size, see ``with_ranks()``.
+    /// n and `parts` must be greater than 0, and n must be at most 34!
+    pub fn split(n: usize, parts: usize) -> Vec<Permutations> {
+        assert!(parts > 0);
+        let total = factorial(n).unwrap();
+        let parts = parts as u128;
+        // The first `total % parts` ranges get one extra rank; this avoids multiplying `total`
+        let (size, extra) = (total / parts, total % parts);
+        let bound = |i: u128| size * i + i.min(extra);
+        (0..parts)
+            .map(|i| Permutations::with_ranks(n, bound(i)..bound(i + 1)))
+            .collect()
+    }
+
     pub fn get_n(&self) -> usize {
         self.n
     }
+
+    /// Number of permutations left to visit, or `None` if it may not fit in ``u128``, i.e. for n above 34.
+    pub fn remaining_u128(&self) -> Option<u128> {
+        if self.is_finished {
+            return Some(0);
+        }
+        let (rank, end) = self.bounds?;
+        Some(end - rank - u128::from(self.is_initiated))
+    }
+
+    /// ``ExactSizeIterator`` view of this iterator, or `None` if the remaining count does not fit in ``usize``,
+    /// e.g. for a fresh iterator with n above 20 on 64-bit targets.
+    pub fn exact(self) -> Option<ExactSize<Permutations>> {
+        usize::try_from(self.remaining_u128()?).ok()?;
+        Some(ExactSize(self))
+    }
+
+    /// Sign of the last permutation returned from the front, maintained in $O(1)$ per step.
+    pub fn sign(&self) -> i8 {
+        if self.is_odd {
+            -1
+        } else {
+            1
+        }
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.advance() {
+            Some(&self.perm)
+        } else {
+            None
+        }
+    }
+
+    /// Steps to the next permutation and returns the position `i` such that entries `i` and `i + 1` were swapped.
+    /// The identity counts as already visited, so the first call performs the first swap.
+    pub fn next_swap(&mut self) -> Option<usize> {
+        self.is_initiated = true;
+        if self.is_finished {
+            return None;
+        }
+        self.step()
+    }
+
+    /// Converts into an iterator over the swap positions, see ``next_swap()``.
+    pub fn transpositions(self) -> Transpositions {
+        Transpositions { perms: self }
+    }
+
+    /// Permutes `items` in place along the SJT order, see ``SlicePermutations``.
+    /// `items` must not be empty!
+    pub fn over<T>(items: &mut [T]) -> SlicePermutations<'_, T> {
+        SlicePermutations {
+            swaps: Permutations::of(items.len()),
+            items,
+        }
+    }
+
+    /// Calls `f` with every remaining permutation without allocating.
+    pub fn for_each_perm<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(perm) = self.next_ref() {
+            f(perm);
+        }
+    }
+
+    /// Moves to the next permutation, which is the identity on the first call.
+    /// Returns `false` once the iterator is exhausted.
+    fn advance(&mut self) -> bool {
+        if !self.is_initiated {
+            self.is_initiated = true;
+            if matches!(self.bounds, Some((start, end)) if start >= end) {
+                self.is_finished = true;
+                return false;
+            }
+            return true;
+        }
+        !self.is_finished && self.step().is_some()
+    }
+
+    /// Performs one SJT transition in $O(1)$ worst-case time and returns the smaller of the two swapped positions.
+    /// Returns `None` without touching `perm` if the last permutation was already reached.
+    fn step(&mut self) -> Option<usize> {
+        if let Some((rank, end)) = &mut self.bounds {
+            if *rank + 1 >= *end {
+                self.is_finished = true;
+                return None;
+            }
+            *rank += 1;
+        }
+        let t = self.focus[0];
+        self.focus[0] = 0;
+        if t == self.n - 1 {
+            self.is_finished = true;
+            return None;
+        }
+        // Move value `n - 1 - t` one place along its direction
+        let v = self.n - 1 - t;
+        let vi = self.inv[v];
+        let vi_new = if self.rising[t] { vi - 1 } else { vi + 1 };
+        let w = self.perm[vi_new];
+        self.perm.swap(vi, vi_new);
+        self.inv[v] = vi_new;
+        self.inv[w] = vi;
+        self.is_odd = !self.is_odd;
+        // Advance the Gray code digit and its focus pointer
+        if self.rising[t] {
+            self.digit[t] += 1;
+        } else {
+            self.digit[t] -= 1;
+        }
+        if self.digit[t] == 0 || self.digit[t] == v {
+            self.rising[t] = !self.rising[t];
+            self.focus[t] = self.focus[t + 1];
+            self.focus[t + 1] = t + 1;
+        }
+        Some(vi.min(vi_new))
+    }
 }
 
 impl Iterator for Permutations {
     type Item = Vec<usize>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if !self.is_initiated {
-            for i in 1..self.n {
-                self.perm[i] = i;
-                self.direction[i] = -1;
+        self.next_ref().map(|perm| perm.to_vec())
+    }
+
+    /// Jumps through ranks with ``with_ranks()`` in $O(n^2)$ time instead of stepping `k` times.
+    fn nth(&mut self, k: usize) -> Option<Self::Item> {
+        let Some((current, end)) = self.bounds.filter(|_| k > self.n && !self.is_finished) else {
+            for _ in 0..k {
+                self.next_ref()?;
             }
+            return self.next();
+        };
+        let target = current + k as u128 + u128::from(self.is_initiated);
+        if target >= end {
             self.is_initiated = true;
-            return Some(self.perm.clone());
-        }
-        if self.is_finished {
+            self.is_finished = true;
             return None;
         }
-        // We know we have a valid perm & direction here, but we may be finished.
-        let rev_perm = inverse_perm(&self.perm);
-        // Find largest element i that has non-zero direction
-        let mut i = self.n;
-        let mut ii = 0;
-        let mut id = 0;
-        for j in (0..self.n).rev() {
-            ii = rev_perm[j];
-            id = self.direction[ii];
-            if id != 0 {
-                i = j;
-                break;
-            }
+        let back = self.back.take();
+        *self = Permutations::with_ranks(self.n, target..end);
+        self.back = back;
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining_u128().map(usize::try_from) {
+            Some(Ok(remaining)) => (remaining, Some(remaining)),
+            _ => (usize::MAX, None),
         }
-        if i == self.n {
-            self.is_finished = true;
+    }
+}
+
+/// The SJT order read backwards is the same order with values `0` and `1` exchanged,
+/// so ``next_back()`` runs a second forward cursor from the other end and relabels its output.
+/// Both ends share the rank bounds, so they meet without overlap.
+impl DoubleEndedIterator for Permutations {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.is_finished || self.remaining_u128() == Some(0) {
             return None;
         }
-        // Swap i along direction i
-        let ii_new = (ii as isize + id as isize) as usize;
-        self.perm.swap(ii, ii_new);
-        self.direction.swap(ii, ii_new);
-        // Update directions
-        if ii_new == 0
-            || ii_new == self.n - 1
-            || self.perm[(ii_new as isize + id as isize) as usize] > i
-        {
-            self.direction[ii_new] = 0;
+        if self.back.is_none() {
+            let back = match self.bounds {
+                Some((_, end)) => {
+                    let total = factorial(self.n).unwrap();
+                    Permutations::with_ranks(self.n, total - end..total)
+                }
+                None => Permutations::of(self.n),
+            };
+            self.back = Some(Box::new(back));
         }
-        for j in (i + 1)..self.n {
-            let ji = rev_perm[j];
-            self.direction[ji] = if ji < ii_new { 1 } else { -1 };
+        if let Some((_, end)) = &mut self.bounds {
+            *end -= 1;
         }
-        Some(self.perm.clone())
+        let n = self.n;
+        let perm = self.back.as_mut()?.next_ref()?;
+        Some(
+            perm.iter()
+                .map(|&i| if i < 2 && n > 1 { 1 - i } else { i })
+                .collect(),
+        )
+    }
+}
+
+/// Wraps an iterator whose remaining count fits in ``usize``, so that it implements ``ExactSizeIterator``.
+/// Built by ``Permutations.exact()`` or ``ShuffledPermutations.exact()``; the count only decreases, so it keeps fitting.
+pub struct ExactSize<I>(I);
+
+impl<I> ExactSize<I> {
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I: Iterator> Iterator for ExactSize<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn nth(&mut self, k: usize) -> Option<Self::Item> {
+        self.0.nth(k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for ExactSize<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for ExactSize<I> {}
+
+/// Implements ``Iterator`` over the adjacent transpositions performed by ``Permutations``.
+/// Yields `n! - 1` positions for a fresh iterator.
+pub struct Transpositions {
+    perms: Permutations,
+}
+
+impl Transpositions {
+    /// Current permutation, i.e. the identity with all yielded swaps applied.
+    pub fn perm(&self) -> &[usize] {
+        &self.perms.perm
+    }
+}
+
+impl Iterator for Transpositions {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.perms.next_swap()
+    }
+}
+
+/// Applies the SJT swaps directly to a caller-owned slice, so ``T`` needs neither ``Clone`` nor ``Copy``.
+///
+/// The slice is visited in its original order first. After exhaustion it is left in the last arrangement,
+/// which is the original order with the first two items swapped.
+pub struct SlicePermutations<'a, T> {
+    items: &'a mut [T],
+    swaps: Permutations,
+}
+
+impl<T> SlicePermutations<'_, T> {
+    /// Rearranges the slice to the next permutation and borrows it.
+    pub fn next_ref(&mut self) -> Option<&[T]> {
+        if !self.swaps.is_initiated {
+            self.swaps.is_initiated = true;
+            return Some(self.items);
+        }
+        let i = self.swaps.next_swap()?;
+        self.items.swap(i, i + 1);
+        Some(self.items)
+    }
+
+    /// Calls `f` with every remaining arrangement of the slice.
+    pub fn for_each_perm<F: FnMut(&[T])>(&mut self, mut f: F) {
+        while let Some(items) = self.next_ref() {
+            f(items);
+        }
+    }
+}
+
+/// Calls `f` with every arrangement of `items`, permuting the slice in place. Does nothing for an empty slice.
+pub fn permute_slice<T, F: FnMut(&[T])>(items: &mut [T], f: F) {
+    if !items.is_empty() {
+        Permutations::over(items).for_each_perm(f);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Permutations;
+    use crate::{
+        factorial, next_permutation, permute_slice, prev_permutation, rank, unrank, Permutation,
+        Permutations,
+    };
+    use std::collections::HashSet;
 
     /// Prints permutations of 4
     #[test]
@@ -116,4 +588,271 @@ mod tests {
             println!("{:?}", perm);
         }
     }
+
+    /// Checks the exact SJT order for 4
+    #[test]
+    fn sjt_order_of_4() {
+        let expected: [[usize; 4]; 24] = [
+            [0, 1, 2, 3],
+            [0, 1, 3, 2],
+            [0, 3, 1, 2],
+            [3, 0, 1, 2],
+            [3, 0, 2, 1],
+            [0, 3, 2, 1],
+            [0, 2, 3, 1],
+            [0, 2, 1, 3],
+            [2, 0, 1, 3],
+            [2, 0, 3, 1],
+            [2, 3, 0, 1],
+            [3, 2, 0, 1],
+            [3, 2, 1, 0],
+            [2, 3, 1, 0],
+            [2, 1, 3, 0],
+            [2, 1, 0, 3],
+            [1, 2, 0, 3],
+            [1, 2, 3, 0],
+            [1, 3, 2, 0],
+            [3, 1, 2, 0],
+            [3, 1, 0, 2],
+            [1, 3, 0, 2],
+            [1, 0, 3, 2],
+            [1, 0, 2, 3],
+        ];
+        let perms: Vec<Vec<usize>> = Permutations::of(4).collect();
+        assert_eq!(perms, expected);
+    }
+
+    /// Checks that all n! permutations appear once and neighbors differ by an adjacent swap
+    #[test]
+    fn all_permutations_with_adjacent_swaps() {
+        for n in 1..=7 {
+            let perms: Vec<Vec<usize>> = Permutations::of(n).collect();
+            assert_eq!(perms.len(), (1..=n).product::<usize>());
+            assert_eq!(perms.iter().collect::<HashSet<_>>().len(), perms.len());
+            for w in perms.windows(2) {
+                let diff: Vec<usize> = (0..n).filter(|&i| w[0][i] != w[1][i]).collect();
+                assert_eq!(diff.len(), 2);
+                assert_eq!(diff[0] + 1, diff[1]);
+            }
+        }
+    }
+
+    /// Checks that the borrowing API walks the same sequence as ``next()``
+    #[test]
+    fn next_ref_matches_next() {
+        let mut streaming = Permutations::of(5);
+        for perm in Permutations::of(5) {
+            assert_eq!(streaming.next_ref(), Some(&perm[..]));
+        }
+        assert_eq!(streaming.next_ref(), None);
+
+        let mut visited = Vec::new();
+        let mut perms = Permutations::of(4);
+        perms.next();
+        perms.for_each_perm(|perm| visited.push(perm.to_vec()));
+        assert_eq!(visited, Permutations::of(4).skip(1).collect::<Vec<_>>());
+    }
+
+    /// Checks that replaying the emitted swaps reproduces the SJT sequence
+    #[test]
+    fn transpositions_replay_sequence() {
+        let mut perm: Vec<usize> = (0..5).collect();
+        let mut expected = Permutations::of(5).skip(1);
+        let mut swaps = Permutations::of(5).transpositions();
+        for i in swaps.by_ref() {
+            perm.swap(i, i + 1);
+            assert_eq!(Some(&perm), expected.next().as_ref());
+        }
+        assert_eq!(expected.next(), None);
+        assert_eq!(swaps.perm(), &perm[..]);
+        assert_eq!(Permutations::of(1).transpositions().count(), 0);
+    }
+
+    /// Checks that slices of non-``Clone`` items follow the index order
+    #[test]
+    fn permute_non_clone_slice() {
+        struct Item(usize);
+        let mut items: Vec<Item> = (0..4).map(Item).collect();
+        let mut indices = Permutations::of(4);
+        permute_slice(&mut items, |view| {
+            let perm: Vec<usize> = view.iter().map(|item| item.0).collect();
+            assert_eq!(indices.next(), Some(perm));
+        });
+        assert_eq!(indices.next(), None);
+
+        let mut words = ["a", "b", "c"];
+        let mut over = Permutations::over(&mut words);
+        assert_eq!(over.next_ref(), Some(&["a", "b", "c"][..]));
+        assert_eq!(over.next_ref(), Some(&["a", "c", "b"][..]));
+    }
+
+    /// Checks C++ semantics of ``next_permutation()`` and ``prev_permutation()``, including repeated items
+    #[test]
+    fn next_and_prev_permutation() {
+        let mut items = [1, 2, 2, 3];
+        let mut seen = vec![items];
+        while next_permutation(&mut items) {
+            seen.push(items);
+        }
+        assert_eq!(seen.len(), 12);
+        assert!(seen.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(items, [1, 2, 2, 3]);
+
+        let mut items = [3, 2, 2, 1];
+        let mut count = 1;
+        while prev_permutation(&mut items) {
+            count += 1;
+        }
+        assert_eq!(count, 12);
+        assert_eq!(items, [3, 2, 2, 1]);
+
+        let mut items = ['b', 'a', 'c'];
+        assert!(prev_permutation(&mut items));
+        assert_eq!(items, ['a', 'c', 'b']);
+        assert!(next_permutation(&mut items));
+        assert_eq!(items, ['b', 'a', 'c']);
+        let mut empty: [u8; 0] = [];
+        assert!(!next_permutation(&mut empty));
+    }
+
+    /// Checks that resuming from any permutation continues the original sequence
+    #[test]
+    fn resume_from_every_permutation() {
+        for n in 1..=6 {
+            let perms: Vec<Vec<usize>> = Permutations::of(n).collect();
+            for (r, perm) in perms.iter().enumerate() {
+                let resumed: Vec<Vec<usize>> = Permutations::from_perm(perm).collect();
+                assert_eq!(resumed, perms[r..]);
+                let mut swaps = Permutations::from_rank(n, r as u128).transpositions();
+                assert_eq!(swaps.by_ref().count(), perms.len() - r - 1);
+                assert_eq!(swaps.perm(), &perms[perms.len() - 1][..]);
+            }
+        }
+    }
+
+    /// Checks that ``nth()`` agrees with stepping, including past the end
+    #[test]
+    fn nth_skips_ahead() {
+        let perms: Vec<Vec<usize>> = Permutations::of(6).collect();
+        let mut iter = Permutations::of(6);
+        assert_eq!(iter.nth(100), Some(perms[100].clone()));
+        assert_eq!(iter.nth(3), Some(perms[104].clone()));
+        assert_eq!(iter.next(), Some(perms[105].clone()));
+        assert_eq!(iter.nth(613), Some(perms[719].clone()));
+        assert_eq!(iter.next(), None);
+        assert_eq!(Permutations::of(6).nth(720), None);
+        let mut iter = Permutations::of(20);
+        assert_eq!(rank(&iter.nth(1_000_000_000).unwrap()), 1_000_000_000);
+        assert_eq!(rank(&iter.next().unwrap()), 1_000_000_001);
+    }
+
+    /// Checks that split chunks concatenate to the full sequence and bound ``nth()``
+    #[test]
+    fn split_into_rank_ranges() {
+        let perms: Vec<Vec<usize>> = Permutations::of(5).collect();
+        for parts in [1, 2, 7, 120, 200] {
+            let chunks = Permutations::split(5, parts);
+            assert_eq!(chunks.len(), parts);
+            assert_eq!(chunks.into_iter().flatten().collect::<Vec<_>>(), perms);
+        }
+        let mut chunk = Permutations::with_ranks(5, 10..40);
+        assert_eq!(chunk.nth(6), Some(perms[16].clone()));
+        assert_eq!(chunk.nth(22), Some(perms[39].clone()));
+        assert_eq!(chunk.next(), None);
+        assert_eq!(Permutations::with_ranks(5, 10..40).nth(30), None);
+        assert_eq!(
+            Permutations::with_ranks(5, 7..27).transpositions().count(),
+            19
+        );
+        assert_eq!(Permutations::with_ranks(5, 120..120).count(), 0);
+
+        for (n, parts) in [(34, 2), (34, 7), (33, 100)] {
+            let chunks = Permutations::split(n, parts);
+            let mut end = 0;
+            for chunk in &chunks {
+                let (start, chunk_end) = chunk.bounds.unwrap();
+                assert_eq!(start, end);
+                assert!(chunk_end - start >= factorial(n).unwrap() / parts as u128);
+                end = chunk_end;
+            }
+            assert_eq!(end, factorial(n).unwrap());
+        }
+    }
+
+    /// Checks the remaining count while iterating, and its limits for large n
+    #[test]
+    fn exact_remaining_count() {
+        let mut perms = Permutations::of(4).exact().unwrap();
+        for remaining in (0..=24).rev() {
+            assert_eq!(perms.len(), remaining);
+            assert_eq!(perms.size_hint(), (remaining, Some(remaining)));
+            perms.next();
+        }
+        assert_eq!(perms.len(), 0);
+        let mut perms = Permutations::with_ranks(5, 10..40);
+        perms.nth(4);
+        assert_eq!(perms.size_hint(), (25, Some(25)));
+        assert_eq!(Permutations::of(4).collect::<Vec<_>>().capacity(), 24);
+
+        let mut perms = Permutations::of(25);
+        perms.next();
+        assert_eq!(perms.remaining_u128(), Some(15511210043330985984000000 - 1));
+        assert_eq!(perms.size_hint(), (usize::MAX, None));
+        assert_eq!(Permutations::of(35).remaining_u128(), None);
+    }
+
+    /// Checks that ``exact()`` is only available while the remaining count fits in ``usize``
+    #[test]
+    fn exact_size_limits() {
+        assert!(Permutations::of(21).exact().is_none());
+        assert_eq!(Permutations::of(21).next_back().unwrap()[..3], [1, 0, 2]);
+
+        let perms = Permutations::with_ranks(21, 0..1000).exact().unwrap();
+        let (index, last) = perms.enumerate().next_back().unwrap();
+        assert_eq!(index, 999);
+        assert_eq!(last, unrank(21, 999));
+        let mut perms = Permutations::of(20).exact().unwrap();
+        assert_eq!(perms.len(), 2432902008176640000);
+        assert_eq!(perms.by_ref().skip(1).next_back().unwrap()[..3], [1, 0, 2]);
+    }
+
+    /// Checks that ``rev()`` is the exact reverse and that both ends meet without overlap
+    #[test]
+    fn double_ended_iteration() {
+        for n in 1..=6 {
+            let perms: Vec<Vec<usize>> = Permutations::of(n).collect();
+            let reversed: Vec<Vec<usize>> = Permutations::of(n).rev().collect();
+            assert_eq!(reversed, perms.iter().rev().cloned().collect::<Vec<_>>());
+            for split in 0..=perms.len() {
+                let mut iter = Permutations::of(n);
+                let front: Vec<Vec<usize>> = iter.by_ref().take(split).collect();
+                let mut back: Vec<Vec<usize>> = iter.by_ref().rev().collect();
+                assert_eq!(iter.next(), None);
+                back.reverse();
+                assert_eq!([front, back].concat(), perms);
+            }
+        }
+        let mut iter = Permutations::with_ranks(5, 30..60);
+        let perms: Vec<Vec<usize>> = Permutations::of(5).collect();
+        assert_eq!(iter.next_back(), Some(perms[59].clone()));
+        assert_eq!(iter.nth(10), Some(perms[40].clone()));
+        assert_eq!(iter.remaining_u128(), Some(18));
+        assert_eq!(iter.next_back(), Some(perms[58].clone()));
+        let mut iter = Permutations::of(40);
+        assert_eq!(iter.next_back().unwrap()[..3], [1, 0, 2]);
+    }
+
+    /// Checks the incrementally maintained sign, also after resuming
+    #[test]
+    fn sign_along_sjt_order() {
+        let mut perms = Permutations::of(5);
+        while let Some(perm) = perms.next() {
+            assert_eq!(perms.sign(), Permutation::new(perm).unwrap().sign());
+        }
+        for r in [0, 1, 37, 118, 119] {
+            let mut perms = Permutations::from_rank(5, r);
+            let perm = perms.next().unwrap();
+            assert_eq!(perms.sign(), Permutation::new(perm).unwrap().sign());
+        }
+    }
 }
@@ -0,0 +1,249 @@
+//! Mixed-radix counting, plain or in reflected Gray code order, and products of permutation spaces built on it.
+
+use crate::{factorial, Permutations};
+
+/// Implements ``Iterator`` over all digit vectors with `digits[i] < radices[i]`, starting from all zeros.
+/// The last digit changes fastest.
+///
+/// ``MixedRadix.of(radices)`` counts in lexicographic order. ``MixedRadix.gray(radices)`` uses the reflected
+/// Gray code order instead, where each step changes one digit by $\pm 1$; the digit is found with focus pointers
+/// (Knuth's Algorithm H), like the transitions of ``Permutations``, so each step is loopless.
+pub struct MixedRadix {
+    radices: Vec<usize>,
+    digits: Vec<usize>,
+    is_gray: bool,
+    /// Digits with radix greater than 1, fastest first; the others never change.
+    active: Vec<usize>,
+    /// `true` while active digit `t` is increasing.
+    rising: Vec<bool>,
+    /// Focus pointers over the active digits; `focus[0]` is the one to change on the next step.
+    focus: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl MixedRadix {
+    /// Each radix must be greater than 0!
+    pub fn of(radices: &[usize]) -> MixedRadix {
+        assert!(radices.iter().all(|&radix| radix > 0));
+        let active: Vec<usize> = (0..radices.len())
+            .rev()
+            .filter(|&d| radices[d] > 1)
+            .collect();
+        MixedRadix {
+            radices: radices.to_vec(),
+            digits: vec![0; radices.len()],
+            is_gray: false,
+            rising: vec![true; active.len()],
+            focus: (0..=active.len()).collect(),
+            active,
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    /// Each radix must be greater than 0!
+    pub fn gray(radices: &[usize]) -> MixedRadix {
+        MixedRadix {
+            is_gray: true,
+            ..MixedRadix::of(radices)
+        }
+    }
+
+    pub fn get_radices(&self) -> &[usize] {
+        &self.radices
+    }
+
+    /// Number of digit vectors, the product of the radices, or `None` if it does not fit in ``u128``.
+    pub fn total(&self) -> Option<u128> {
+        self.radices
+            .iter()
+            .try_fold(1u128, |total, &radix| total.checked_mul(radix as u128))
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_initiated {
+            self.next_change()?;
+        }
+        self.is_initiated = true;
+        Some(&self.digits)
+    }
+
+    /// Steps to the next digit vector and returns the index of the digit that changed.
+    /// When counting, all later digits wrap around to 0; in Gray code order, no other digit changes.
+    /// The all-zero vector counts as already visited, so the first call performs the first step.
+    pub fn next_change(&mut self) -> Option<usize> {
+        self.is_initiated = true;
+        if self.is_finished {
+            return None;
+        }
+        let change = if self.is_gray {
+            self.step_gray()
+        } else {
+            self.step_counter()
+        };
+        self.is_finished = change.is_none();
+        change
+    }
+
+    fn step_counter(&mut self) -> Option<usize> {
+        let d = (0..self.digits.len())
+            .rev()
+            .find(|&d| self.digits[d] + 1 < self.radices[d])?;
+        self.digits[d] += 1;
+        for digit in &mut self.digits[d + 1..] {
+            *digit = 0;
+        }
+        Some(d)
+    }
+
+    fn step_gray(&mut self) -> Option<usize> {
+        let t = self.focus[0];
+        self.focus[0] = 0;
+        if t == self.active.len() {
+            return None;
+        }
+        let d = self.active[t];
+        if self.rising[t] {
+            self.digits[d] += 1;
+        } else {
+            self.digits[d] -= 1;
+        }
+        if self.digits[d] == 0 || self.digits[d] == self.radices[d] - 1 {
+            self.rising[t] = !self.rising[t];
+            self.focus[t] = self.focus[t + 1];
+            self.focus[t + 1] = t + 1;
+        }
+        Some(d)
+    }
+}
+
+impl Iterator for MixedRadix {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|digits| digits.to_vec())
+    }
+}
+
+/// Implements ``Iterator`` over the product of the permutation spaces of `0..n` for each given n,
+/// i.e. one permutation per factor, starting with all identities.
+///
+/// Factor `i` is driven by Gray code digit `i` of ``MixedRadix.gray()`` with radix $n_i!$, so each step
+/// moves one factor one position forward or backward along its SJT order, which is one adjacent swap.
+/// The SJT order read backwards is the SJT order with values `0` and `1` exchanged, so a backward sweep
+/// replays the swaps of a fresh ``Permutations``.
+pub struct PermutationProduct {
+    counter: MixedRadix,
+    /// Source of the swaps for the current sweep of each factor.
+    sweeps: Vec<Permutations>,
+    perms: Vec<Vec<usize>>,
+}
+
+impl PermutationProduct {
+    /// Each n must be greater than 0, and $n!$ must fit in ``usize``!
+    pub fn of(ns: &[usize]) -> PermutationProduct {
+        let radices: Vec<usize> = ns
+            .iter()
+            .map(|&n| usize::try_from(factorial(n).unwrap()).unwrap())
+            .collect();
+        PermutationProduct {
+            counter: MixedRadix::gray(&radices),
+            sweeps: ns.iter().map(|&n| Permutations::of(n)).collect(),
+            perms: ns.iter().map(|&n| (0..n).collect()).collect(),
+        }
+    }
+
+    /// Number of combined states, or `None` if it does not fit in ``u128``.
+    pub fn total(&self) -> Option<u128> {
+        self.counter.total()
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffers instead of cloning them.
+    pub fn next_ref(&mut self) -> Option<&[Vec<usize>]> {
+        if self.counter.is_initiated {
+            self.next_change()?;
+        }
+        self.counter.is_initiated = true;
+        Some(&self.perms)
+    }
+
+    /// Steps to the next state and returns the factor that changed, and the position `i` such that
+    /// its entries `i` and `i + 1` were swapped. The first state counts as already visited.
+    pub fn next_change(&mut self) -> Option<(usize, usize)> {
+        let factor = self.counter.next_change()?;
+        let i = match self.sweeps[factor].next_swap() {
+            Some(i) => i,
+            None => {
+                // The factor turns around: start the sweep in the other direction
+                self.sweeps[factor] = Permutations::of(self.perms[factor].len());
+                self.sweeps[factor].next_swap()?
+            }
+        };
+        self.perms[factor].swap(i, i + 1);
+        Some((factor, i))
+    }
+}
+
+impl Iterator for PermutationProduct {
+    type Item = Vec<Vec<usize>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|perms| perms.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MixedRadix, PermutationProduct};
+    use std::collections::HashSet;
+
+    /// Checks lexicographic counting and the one-digit steps of the Gray code order
+    #[test]
+    fn mixed_radix_orders() {
+        for radices in [
+            &[3, 1, 2][..],
+            &[2, 2, 2],
+            &[4],
+            &[1],
+            &[1, 1],
+            &[3, 4, 2, 1, 5],
+        ] {
+            let total: usize = radices.iter().product();
+            assert_eq!(MixedRadix::of(radices).total(), Some(total as u128));
+            let counted: Vec<Vec<usize>> = MixedRadix::of(radices).collect();
+            assert_eq!(counted.len(), total);
+            assert!(counted.windows(2).all(|w| w[0] < w[1]));
+
+            let gray: Vec<Vec<usize>> = MixedRadix::gray(radices).collect();
+            assert_eq!(gray.iter().collect::<HashSet<_>>().len(), total);
+            let mut changes = MixedRadix::gray(radices);
+            for w in gray.windows(2) {
+                let d = changes.next_change().unwrap();
+                assert!((0..radices.len()).all(|i| i == d || w[0][i] == w[1][i]));
+                assert_eq!(w[0][d].abs_diff(w[1][d]), 1);
+            }
+            assert_eq!(changes.next_change(), None);
+        }
+    }
+
+    /// Checks that the product visits every combination once, one adjacent swap at a time
+    #[test]
+    fn permutation_product() {
+        let ns = [3, 1, 4, 2];
+        let states: Vec<Vec<Vec<usize>>> = PermutationProduct::of(&ns).collect();
+        assert_eq!(PermutationProduct::of(&ns).total(), Some(6 * 24 * 2));
+        assert_eq!(states.len(), 6 * 24 * 2);
+        assert_eq!(states.iter().collect::<HashSet<_>>().len(), states.len());
+
+        let mut product = PermutationProduct::of(&ns);
+        for w in states.windows(2) {
+            let (factor, i) = product.next_change().unwrap();
+            let mut expected = w[0].clone();
+            expected[factor].swap(i, i + 1);
+            assert_eq!(expected, w[1]);
+        }
+        assert_eq!(product.next_change(), None);
+    }
+}
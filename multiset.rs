@@ -0,0 +1,91 @@
+//! Permutations of a multiset, i.e. with repeated elements.
+
+use crate::{multinomial, next_permutation};
+
+/// Implements ``Iterator`` over the distinct arrangements of a multiset, in lexicographic order.
+///
+/// Uses Knuth's Algorithm L (``next_permutation()``), which never produces the same arrangement twice,
+/// so no deduplication is needed. Each step has $O(n)$ worst-case time complexity.
+pub struct MultisetPermutations {
+    perm: Vec<usize>,
+    total: Option<u128>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl MultisetPermutations {
+    /// Value `i` appears `multiplicities[i]` times in each arrangement. The multiset must not be empty!
+    pub fn of(multiplicities: &[usize]) -> MultisetPermutations {
+        let perm: Vec<usize> = multiplicities
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &m)| std::iter::repeat_n(i, m))
+            .collect();
+        assert!(!perm.is_empty());
+        MultisetPermutations {
+            perm,
+            total: multinomial(multiplicities),
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.perm.len()
+    }
+
+    /// Number of distinct arrangements (the multinomial coefficient), or `None` if it does not fit in ``u128``.
+    pub fn total(&self) -> Option<u128> {
+        self.total
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if !self.is_initiated {
+            self.is_initiated = true;
+            return Some(&self.perm);
+        }
+        if self.is_finished || !next_permutation(&mut self.perm) {
+            self.is_finished = true;
+            return None;
+        }
+        Some(&self.perm)
+    }
+
+    /// Calls `f` with every remaining arrangement without allocating.
+    pub fn for_each_perm<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(perm) = self.next_ref() {
+            f(perm);
+        }
+    }
+}
+
+impl Iterator for MultisetPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|perm| perm.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MultisetPermutations, Permutations};
+    use std::collections::HashSet;
+
+    /// Checks against deduplicated full permutations
+    #[test]
+    fn matches_deduplicated_permutations() {
+        for multiplicities in [&[1, 1, 1][..], &[2, 1, 3], &[0, 4], &[2, 0, 2, 1]] {
+            let items: Vec<usize> = MultisetPermutations::of(multiplicities).next().unwrap();
+            let expected: HashSet<Vec<usize>> = Permutations::of(items.len())
+                .map(|perm| perm.iter().map(|&i| items[i]).collect())
+                .collect();
+            let perms = MultisetPermutations::of(multiplicities);
+            assert_eq!(perms.total(), Some(expected.len() as u128));
+            let perms: Vec<Vec<usize>> = perms.collect();
+            assert_eq!(perms.len(), expected.len());
+            assert_eq!(perms.into_iter().collect::<HashSet<_>>(), expected);
+        }
+    }
+}
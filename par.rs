@@ -0,0 +1,91 @@
+//! Parallel iteration with ``rayon``, built on splitting the SJT order into rank ranges.
+
+use crate::{factorial, ExactSize, Permutations};
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+/// Implements ``IndexedParallelIterator`` over permutations of `0..n`, in SJT order when collected.
+pub struct ParPermutations {
+    n: usize,
+    start: u128,
+    end: u128,
+}
+
+impl Permutations {
+    /// Parallel counterpart of ``Permutations.of(n)``.
+    /// n must be greater than 0 and $n!$ must fit in ``usize``!
+    pub fn par_of(n: usize) -> ParPermutations {
+        let end = factorial(n).unwrap();
+        assert!(n > 0 && end <= usize::MAX as u128);
+        ParPermutations { n, start: 0, end }
+    }
+}
+
+impl ParallelIterator for ParPermutations {
+    type Item = Vec<usize>;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl IndexedParallelIterator for ParPermutations {
+    fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self)
+    }
+}
+
+impl Producer for ParPermutations {
+    type Item = Vec<usize>;
+    type IntoIter = ExactSize<Permutations>;
+
+    fn into_iter(self) -> ExactSize<Permutations> {
+        ExactSize(Permutations::with_ranks(self.n, self.start..self.end))
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index as u128;
+        (
+            ParPermutations { end: mid, ..self },
+            ParPermutations { start: mid, ..self },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Permutations;
+    use rayon::prelude::*;
+
+    /// Checks that parallel collection preserves the SJT order
+    #[test]
+    fn parallel_matches_sequential() {
+        let perms: Vec<Vec<usize>> = Permutations::par_of(7).collect();
+        assert_eq!(perms, Permutations::of(7).collect::<Vec<_>>());
+        let reversed: Vec<Vec<usize>> = Permutations::par_of(5).rev().collect();
+        assert_eq!(
+            reversed,
+            Permutations::of(5)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Permutations::par_of(8).filter(|perm| perm[0] == 0).count(),
+            5040
+        );
+    }
+}
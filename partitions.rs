@@ -0,0 +1,226 @@
+//! Set partitions of `0..n` as restricted growth strings.
+
+use crate::{bell, stirling2};
+
+/// Implements ``Iterator`` over the partitions of `0..n` into blocks, as restricted growth strings:
+/// entry `i` is the block of element `i`, and blocks are numbered in order of their smallest element.
+///
+/// ``SetPartitions.of(n)`` uses a minimal-change order where each step moves one element to another block.
+/// Each entry sweeps through its allowed blocks in one of two orders, `0, m + 1, m, ..., 1` or `1, 2, ..., m + 1, 0`
+/// where `m` is the largest block before it, alternating like a reflected Gray code. Both orders end where the
+/// other starts, so finishing a sweep never changes the entry. Each step has $O(n)$ time complexity.
+///
+/// ``SetPartitions.with_blocks(n, k)`` restricts to exactly `k` blocks, in lexicographic order instead.
+pub struct SetPartitions {
+    n: usize,
+    /// Number of blocks for ``with_blocks()``, `None` for all partitions in minimal-change order.
+    k: Option<usize>,
+    rgs: Vec<usize>,
+    /// Largest entry before each position.
+    prefix_max: Vec<usize>,
+    /// `true` while entry `i` sweeps `0, m + 1, m, ..., 1`, `false` for `1, 2, ..., m + 1, 0`.
+    descending: Vec<bool>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl SetPartitions {
+    /// n must be greater than 0!
+    pub fn of(n: usize) -> SetPartitions {
+        assert!(n > 0);
+        SetPartitions {
+            n,
+            k: None,
+            rgs: vec![0; n],
+            prefix_max: vec![0; n],
+            descending: vec![true; n],
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    /// k must be greater than 0 and must not exceed n!
+    pub fn with_blocks(n: usize, k: usize) -> SetPartitions {
+        assert!(k > 0 && k <= n);
+        let mut partitions = SetPartitions::of(n);
+        partitions.k = Some(k);
+        // Smallest string with k blocks: all zeros, then 1, 2, ..., k - 1 at the end
+        for b in 1..k {
+            partitions.rgs[n - k + b] = b;
+        }
+        partitions.update_prefix_max(0);
+        partitions
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of partitions to visit in total, i.e. the Bell number or the Stirling number of the second kind,
+    /// or `None` if it does not fit in ``u128``.
+    pub fn total(&self) -> Option<u128> {
+        match self.k {
+            Some(k) => stirling2(self.n, k),
+            None => bell(self.n),
+        }
+    }
+
+    /// Streaming alternative to ``next()``: borrows the internal buffer instead of cloning it.
+    pub fn next_ref(&mut self) -> Option<&[usize]> {
+        if self.is_initiated && !self.step() {
+            self.is_finished = true;
+        }
+        self.is_initiated = true;
+        if self.is_finished {
+            None
+        } else {
+            Some(&self.rgs)
+        }
+    }
+
+    /// Calls `f` with every remaining partition without allocating.
+    pub fn for_each_partition<F: FnMut(&[usize])>(&mut self, mut f: F) {
+        while let Some(rgs) = self.next_ref() {
+            f(rgs);
+        }
+    }
+
+    fn update_prefix_max(&mut self, from: usize) {
+        for i in from.max(1)..self.n {
+            self.prefix_max[i] = self.prefix_max[i - 1].max(self.rgs[i - 1]);
+        }
+    }
+
+    /// Moves to the next partition. Returns `false` if there is none.
+    fn step(&mut self) -> bool {
+        if self.is_finished {
+            return false;
+        }
+        match self.k {
+            Some(k) => self.step_lexicographic(k),
+            None => self.step_minimal_change(),
+        }
+    }
+
+    fn step_minimal_change(&mut self) -> bool {
+        for i in (1..self.n).rev() {
+            let (a, m) = (self.rgs[i], self.prefix_max[i]);
+            let next = match (self.descending[i], a) {
+                (true, 0) => Some(m + 1),
+                (true, 1) => None,
+                (true, _) => Some(a - 1),
+                (false, 0) => None,
+                (false, _) if a > m => Some(0),
+                (false, _) => Some(a + 1),
+            };
+            match next {
+                Some(b) => {
+                    self.rgs[i] = b;
+                    self.update_prefix_max(i + 1);
+                    return true;
+                }
+                // The sweep is over, and the next one starts at the same entry
+                None => self.descending[i] = !self.descending[i],
+            }
+        }
+        false
+    }
+
+    fn step_lexicographic(&mut self, k: usize) -> bool {
+        for i in (1..self.n).rev() {
+            let b = self.rgs[i] + 1;
+            let blocks = self.prefix_max[i].max(b) + 1;
+            // The entry may open at most one new block, and the rest must be able to open the missing ones
+            if b <= self.prefix_max[i] + 1 && blocks <= k && self.n - 1 - i >= k - blocks {
+                self.rgs[i] = b;
+                // Smallest completion: zeros, then the missing blocks in increasing order at the end
+                let missing = k - blocks;
+                for j in i + 1..self.n {
+                    let from_end = self.n - j;
+                    self.rgs[j] = if from_end <= missing { k - from_end } else { 0 };
+                }
+                self.update_prefix_max(i + 1);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Iterator for SetPartitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().map(|rgs| rgs.to_vec())
+    }
+}
+
+/// Lists the blocks of the partition described by a restricted growth string, each in ascending order.
+pub fn rgs_to_blocks(rgs: &[usize]) -> Vec<Vec<usize>> {
+    let mut blocks: Vec<Vec<usize>> = Vec::new();
+    for (i, &b) in rgs.iter().enumerate() {
+        if b == blocks.len() {
+            blocks.push(Vec::new());
+        }
+        blocks[b].push(i);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bell, rgs_to_blocks, stirling2, SetPartitions};
+    use std::collections::HashSet;
+
+    /// Checks that a string is a restricted growth string
+    fn is_rgs(rgs: &[usize]) -> bool {
+        let mut max = 0;
+        rgs.iter().enumerate().all(|(i, &b)| {
+            let valid = if i == 0 { b == 0 } else { b <= max + 1 };
+            max = max.max(b);
+            valid
+        })
+    }
+
+    /// Checks completeness, Bell numbers and the one-element moves of the minimal-change order
+    #[test]
+    fn minimal_change_order() {
+        for n in 1..=9 {
+            let partitions = SetPartitions::of(n);
+            assert_eq!(partitions.total(), bell(n));
+            let partitions: Vec<Vec<usize>> = partitions.collect();
+            assert!(partitions.iter().all(|rgs| is_rgs(rgs)));
+            assert_eq!(
+                partitions.iter().collect::<HashSet<_>>().len(),
+                partitions.len()
+            );
+            assert_eq!(Some(partitions.len() as u128), bell(n));
+            for w in partitions.windows(2) {
+                assert_eq!((0..n).filter(|&i| w[0][i] != w[1][i]).count(), 1);
+            }
+        }
+    }
+
+    /// Checks the fixed block count against filtering, and Stirling numbers
+    #[test]
+    fn fixed_number_of_blocks() {
+        for n in 1..=8 {
+            let all: Vec<Vec<usize>> = SetPartitions::of(n).collect();
+            for k in 1..=n {
+                let mut expected: Vec<Vec<usize>> = all
+                    .iter()
+                    .filter(|rgs| rgs.iter().max() == Some(&(k - 1)))
+                    .cloned()
+                    .collect();
+                expected.sort();
+                let partitions = SetPartitions::with_blocks(n, k);
+                assert_eq!(partitions.total(), stirling2(n, k));
+                assert_eq!(partitions.collect::<Vec<_>>(), expected);
+            }
+        }
+        assert_eq!(
+            rgs_to_blocks(&[0, 1, 0, 2, 1]),
+            [vec![0, 2], vec![1, 4], vec![3]]
+        );
+    }
+}
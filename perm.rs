@@ -0,0 +1,185 @@
+//! Validated permutation values and their group operations.
+
+use crate::inverse_perm;
+use std::fmt;
+use std::ops::Mul;
+
+/// A bijection on `0..n` in one-line form: position `i` maps to `self[i]`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Permutation {
+    perm: Vec<usize>,
+}
+
+/// Error for a one-line form that is not a bijection on `0..n`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotAPermutation {
+    /// First position whose entry is out of range or repeated.
+    pub position: usize,
+}
+
+impl fmt::Display for NotAPermutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "entry at position {} is out of range or repeated",
+            self.position
+        )
+    }
+}
+
+impl std::error::Error for NotAPermutation {}
+
+impl Permutation {
+    /// Checks that `perm` is a bijection on `0..n` in $O(n)$ time.
+    pub fn new(perm: Vec<usize>) -> Result<Permutation, NotAPermutation> {
+        let mut seen = vec![false; perm.len()];
+        for (position, &i) in perm.iter().enumerate() {
+            if i >= perm.len() || seen[i] {
+                return Err(NotAPermutation { position });
+            }
+            seen[i] = true;
+        }
+        Ok(Permutation { perm })
+    }
+
+    pub fn identity(n: usize) -> Permutation {
+        Permutation {
+            perm: (0..n).collect(),
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.perm.len()
+    }
+
+    pub fn as_slice(&self) -> &[usize] {
+        &self.perm
+    }
+
+    pub fn into_vec(self) -> Vec<usize> {
+        self.perm
+    }
+
+    /// Function composition: `i` maps to `self[other[i]]`, i.e. `other` is applied first.
+    /// Both permutations must have the same n!
+    pub fn compose(&self, other: &Permutation) -> Permutation {
+        assert_eq!(self.get_n(), other.get_n());
+        Permutation {
+            perm: other.perm.iter().map(|&i| self.perm[i]).collect(),
+        }
+    }
+
+    pub fn inverse(&self) -> Permutation {
+        Permutation {
+            perm: inverse_perm(&self.perm),
+        }
+    }
+
+    /// Composes the permutation with itself `k` times, inverting first for negative `k`.
+    /// Uses repeated squaring, so it has $O(n \log |k|)$ time complexity.
+    pub fn pow(&self, k: i64) -> Permutation {
+        let mut base = if k < 0 { self.inverse() } else { self.clone() };
+        let mut k = k.unsigned_abs();
+        let mut result = Permutation::identity(self.get_n());
+        while k > 0 {
+            if k % 2 == 1 {
+                result = result.compose(&base);
+            }
+            base = base.compose(&base);
+            k /= 2;
+        }
+        result
+    }
+
+    /// Rearranges `items` so that position `i` holds `items[self[i]]`, the same view ``Permutations`` indices give.
+    /// `items` must have length n!
+    pub fn apply_to_slice<T: Clone>(&self, items: &[T]) -> Vec<T> {
+        assert_eq!(self.get_n(), items.len());
+        self.perm.iter().map(|&i| items[i].clone()).collect()
+    }
+}
+
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = NotAPermutation;
+
+    fn try_from(perm: Vec<usize>) -> Result<Permutation, NotAPermutation> {
+        Permutation::new(perm)
+    }
+}
+
+impl From<Permutation> for Vec<usize> {
+    fn from(perm: Permutation) -> Vec<usize> {
+        perm.perm
+    }
+}
+
+impl AsRef<[usize]> for Permutation {
+    fn as_ref(&self) -> &[usize] {
+        &self.perm
+    }
+}
+
+/// Same as ``compose()``.
+impl Mul for &Permutation {
+    type Output = Permutation;
+
+    fn mul(self, other: &Permutation) -> Permutation {
+        self.compose(other)
+    }
+}
+
+/// Same as ``compose()``.
+impl Mul for Permutation {
+    type Output = Permutation;
+
+    fn mul(self, other: Permutation) -> Permutation {
+        self.compose(&other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NotAPermutation, Permutation, Permutations};
+
+    /// Checks validation of the one-line form
+    #[test]
+    fn rejects_non_bijections() {
+        assert!(Permutation::new(vec![2, 0, 1]).is_ok());
+        assert!(Permutation::new(vec![]).is_ok());
+        assert_eq!(
+            Permutation::new(vec![0, 3, 1]),
+            Err(NotAPermutation { position: 1 })
+        );
+        assert_eq!(
+            Permutation::try_from(vec![1, 0, 1]),
+            Err(NotAPermutation { position: 2 })
+        );
+    }
+
+    /// Checks group laws over all permutations of 4
+    #[test]
+    fn group_operations() {
+        let perms: Vec<Permutation> = Permutations::of(4)
+            .map(|perm| Permutation::new(perm).unwrap())
+            .collect();
+        let id = Permutation::identity(4);
+        for a in &perms {
+            assert_eq!(a * &a.inverse(), id);
+            assert_eq!(&a.inverse() * a, id);
+            assert_eq!(a.pow(0), id);
+            assert_eq!(a.pow(3), &(a * a) * a);
+            assert_eq!(a.pow(-2), a.inverse().pow(2));
+            assert_eq!(a.pow(12), id);
+            for b in &perms {
+                let items = ['w', 'x', 'y', 'z'];
+                assert_eq!(
+                    (a * b).apply_to_slice(&items),
+                    b.apply_to_slice(&a.apply_to_slice(&items))
+                );
+            }
+        }
+        let a = Permutation::new(vec![1, 2, 0]).unwrap();
+        let b = Permutation::new(vec![0, 2, 1]).unwrap();
+        assert_eq!((a * b).into_vec(), [1, 0, 2]);
+    }
+}
@@ -0,0 +1,181 @@
+//! Random sampling, available with the `rand` feature.
+
+use crate::rank::MAX_N;
+use crate::{factorial, unrank, ExactSize};
+use rand::{Rng, RngExt};
+
+/// Uniformly random permutation of `0..n` by Fisher-Yates shuffle. Has $O(n)$ time complexity.
+pub fn random_permutation<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    let mut perm: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        perm.swap(i, rng.random_range(0..=i));
+    }
+    perm
+}
+
+/// Uniformly random derangement of `0..n` by rejection; about $e$ shuffles are needed on average.
+/// n must not be 1!
+pub fn random_derangement<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    assert!(n != 1);
+    loop {
+        let perm = random_permutation(n, rng);
+        if perm.iter().enumerate().all(|(ii, &i)| ii != i) {
+            return perm;
+        }
+    }
+}
+
+/// Uniformly random rank in `0..n!`, e.g. for ``unrank()`` or ``Permutations.from_rank()``.
+/// n must not exceed 34!
+pub fn random_rank<R: Rng + ?Sized>(n: usize, rng: &mut R) -> u128 {
+    rng.random_range(0..factorial(n).unwrap())
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+/// Implements ``Iterator`` over all permutations of `0..n` in a pseudo-random order, each exactly once.
+///
+/// Ranks `0..n!` are mapped through a keyed Feistel network on the smallest even number of bits that fits them,
+/// cycle-walking past values out of range, and then unranked. This is a bijection, so no visited set is kept;
+/// each step has $O(n^2)$ time complexity.
+pub struct ShuffledPermutations {
+    n: usize,
+    keys: [u64; 4],
+    half_bits: u32,
+    index: u128,
+    total: u128,
+}
+
+impl ShuffledPermutations {
+    /// Draws the Feistel keys from `rng`, so a seeded `rng` gives a reproducible order.
+    /// n must be greater than 0 and must not exceed 34!
+    pub fn new<R: Rng + ?Sized>(n: usize, rng: &mut R) -> ShuffledPermutations {
+        assert!(n > 0 && n <= MAX_N);
+        let total = factorial(n).unwrap();
+        let bits = u128::BITS - (total - 1).leading_zeros();
+        ShuffledPermutations {
+            n,
+            keys: [
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+            ],
+            half_bits: bits.div_ceil(2),
+            index: 0,
+            total,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of permutations left to visit.
+    pub fn remaining_u128(&self) -> u128 {
+        self.total - self.index
+    }
+
+    /// ``ExactSizeIterator`` view of this iterator, or `None` if the remaining count does not fit in ``usize``.
+    pub fn exact(self) -> Option<ExactSize<ShuffledPermutations>> {
+        usize::try_from(self.remaining_u128()).ok()?;
+        Some(ExactSize(self))
+    }
+
+    /// The bijection on `0..n!` that orders the ranks.
+    fn shuffle_rank(&self, mut rank: u128) -> u128 {
+        let mask = (1u128 << self.half_bits) - 1;
+        loop {
+            let (mut left, mut right) = (rank >> self.half_bits, rank & mask);
+            for key in self.keys {
+                let round = u128::from(splitmix64(right as u64 ^ key)) & mask;
+                (left, right) = (right, left ^ round);
+            }
+            rank = (left << self.half_bits) | right;
+            if rank < self.total {
+                return rank;
+            }
+        }
+    }
+}
+
+impl Iterator for ShuffledPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.total {
+            return None;
+        }
+        let rank = self.shuffle_rank(self.index);
+        self.index += 1;
+        Some(unrank(self.n, rank))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining_u128()) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        random_derangement, random_permutation, random_rank, Permutation, ShuffledPermutations,
+    };
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::{HashMap, HashSet};
+
+    /// Checks validity and rough uniformity of the samplers
+    #[test]
+    fn samplers() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut counts: HashMap<Vec<usize>, usize> = HashMap::new();
+        for _ in 0..6000 {
+            *counts.entry(random_permutation(3, &mut rng)).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 6);
+        assert!(counts.values().all(|&count| (800..1200).contains(&count)));
+        for _ in 0..100 {
+            let perm = Permutation::new(random_derangement(6, &mut rng)).unwrap();
+            assert_eq!(perm.fixed_points(), 0);
+            assert!(random_rank(20, &mut rng) < 2432902008176640000);
+        }
+        assert_eq!(random_derangement(0, &mut rng), []);
+    }
+
+    /// Checks that the shuffled order visits every permutation once and is reproducible
+    #[test]
+    fn shuffled_visits_each_once() {
+        for n in 1..=6 {
+            let perms: Vec<Vec<usize>> =
+                ShuffledPermutations::new(n, &mut StdRng::seed_from_u64(1)).collect();
+            assert_eq!(
+                perms.iter().collect::<HashSet<_>>().len(),
+                (1..=n).product::<usize>()
+            );
+            let again: Vec<Vec<usize>> =
+                ShuffledPermutations::new(n, &mut StdRng::seed_from_u64(1)).collect();
+            assert_eq!(perms, again);
+        }
+        let mut shuffled = ShuffledPermutations::new(34, &mut StdRng::seed_from_u64(2));
+        assert_eq!(shuffled.size_hint(), (usize::MAX, None));
+        assert_eq!(shuffled.next().unwrap().len(), 34);
+        assert!(ShuffledPermutations::new(21, &mut StdRng::seed_from_u64(3))
+            .exact()
+            .is_none());
+        let mut shuffled = ShuffledPermutations::new(5, &mut StdRng::seed_from_u64(3))
+            .exact()
+            .unwrap();
+        shuffled.next();
+        assert_eq!(shuffled.len(), 119);
+        assert_eq!(shuffled.enumerate().last().unwrap().0, 118);
+    }
+}
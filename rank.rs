@@ -0,0 +1,114 @@
+//! Ranking and unranking permutations of `0..n`, for both SJT and lexicographic order.
+//!
+//! Ranks are ``u128``, so `n` must not exceed 34: $35!$ does not fit.
+
+use crate::factorial;
+
+/// Largest `n` for which all ranks fit in ``u128``.
+pub(crate) const MAX_N: usize = 34;
+
+/// Index of `perm` in the sequence generated by ``Permutations.of(n)``. Has $O(n^2)$ time complexity.
+/// `perm` must be a permutation of `0..n`!
+pub fn rank(perm: &[usize]) -> u128 {
+    assert!(perm.len() <= MAX_N);
+    let mut rank: u128 = 0;
+    // Build the rank of the sub-permutation of values `0..=v` for increasing v: value `v` sweeps
+    // right to left over each even-ranked sub-permutation of `0..v`, and left to right over each odd one.
+    for v in 1..perm.len() {
+        let vi = perm.iter().position(|&i| i == v).unwrap();
+        let smaller_left = perm[..vi].iter().filter(|&&i| i < v).count();
+        let offset = if rank.is_multiple_of(2) {
+            v - smaller_left
+        } else {
+            smaller_left
+        };
+        rank = rank * (v as u128 + 1) + offset as u128;
+    }
+    rank
+}
+
+/// The `rank`-th permutation generated by ``Permutations.of(n)``. Has $O(n^2)$ time complexity.
+/// n must be greater than 0 and `rank` must be less than $n!$!
+pub fn unrank(n: usize, rank: u128) -> Vec<usize> {
+    assert!(n > 0 && n <= MAX_N);
+    assert!(rank < factorial(n).unwrap());
+    // Peel off the offset of each value from the largest one down, see ``rank()``
+    let mut smaller_left = vec![0; n];
+    let mut rank = rank;
+    for v in (1..n).rev() {
+        let offset = (rank % (v as u128 + 1)) as usize;
+        rank /= v as u128 + 1;
+        smaller_left[v] = if rank.is_multiple_of(2) {
+            v - offset
+        } else {
+            offset
+        };
+    }
+    let mut perm = Vec::with_capacity(n);
+    for (v, &vi) in smaller_left.iter().enumerate() {
+        perm.insert(vi, v);
+    }
+    perm
+}
+
+/// Index of `perm` in lexicographic order, computed from its Lehmer code. Has $O(n^2)$ time complexity.
+/// `perm` must be a permutation of `0..n`!
+pub fn lex_rank(perm: &[usize]) -> u128 {
+    assert!(perm.len() <= MAX_N);
+    let mut rank: u128 = 0;
+    for (ii, &i) in perm.iter().enumerate() {
+        let lehmer = perm[ii + 1..].iter().filter(|&&j| j < i).count();
+        rank = rank * (perm.len() - ii) as u128 + lehmer as u128;
+    }
+    rank
+}
+
+/// The `rank`-th permutation of `0..n` in lexicographic order. Has $O(n^2)$ time complexity.
+/// n must be greater than 0 and `rank` must be less than $n!$!
+pub fn lex_unrank(n: usize, rank: u128) -> Vec<usize> {
+    assert!(n > 0 && n <= MAX_N);
+    assert!(rank < factorial(n).unwrap());
+    // Digits of the factorial number system are the Lehmer code, least significant last
+    let mut lehmer = vec![0; n];
+    let mut rank = rank;
+    for (ii, digit) in lehmer.iter_mut().enumerate().rev() {
+        *digit = (rank % (n - ii) as u128) as usize;
+        rank /= (n - ii) as u128;
+    }
+    let mut unused: Vec<usize> = (0..n).collect();
+    lehmer
+        .into_iter()
+        .map(|digit| unused.remove(digit))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lex_rank, lex_unrank, rank, unrank, LexPermutations, Permutations};
+
+    /// Checks that ranks are the positions in the generated sequences, and unranking inverts them
+    #[test]
+    fn ranks_match_iteration_order() {
+        for n in 1..=6 {
+            for (r, perm) in Permutations::of(n).enumerate() {
+                assert_eq!(rank(&perm), r as u128);
+                assert_eq!(unrank(n, r as u128), perm);
+            }
+            for (r, perm) in LexPermutations::of(n).enumerate() {
+                assert_eq!(lex_rank(&perm), r as u128);
+                assert_eq!(lex_unrank(n, r as u128), perm);
+            }
+        }
+    }
+
+    /// Checks round trips at the largest supported n
+    #[test]
+    fn round_trip_at_34() {
+        let last = crate::factorial(34).unwrap() - 1;
+        for r in [0, 1, 12345678901234567890, last / 3, last] {
+            assert_eq!(rank(&unrank(34, r)), r);
+            assert_eq!(lex_rank(&lex_unrank(34, r)), r);
+        }
+        assert_eq!(lex_unrank(34, last), (0..34).rev().collect::<Vec<_>>());
+    }
+}
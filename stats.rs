@@ -0,0 +1,122 @@
+//! Permutation statistics.
+
+use crate::count::gcd;
+use crate::Permutation;
+
+/// Sorts `perm` with merge sort through `buffer`, returning the number of inversions.
+fn sort_counting_inversions(perm: &mut [usize], buffer: &mut [usize]) -> usize {
+    let n = perm.len();
+    let mut inversions = 0;
+    // Bottom-up passes merging runs of width 1, 2, 4, ...
+    let mut width = 1;
+    while width < n {
+        for start in (0..n).step_by(2 * width) {
+            let mid = (start + width).min(n);
+            let end = (start + 2 * width).min(n);
+            let (mut i, mut j) = (start, mid);
+            for slot in &mut buffer[start..end] {
+                if j == end || (i < mid && perm[i] < perm[j]) {
+                    *slot = perm[i];
+                    i += 1;
+                } else {
+                    // Everything left in the first run is larger than perm[j]
+                    inversions += mid - i;
+                    *slot = perm[j];
+                    j += 1;
+                }
+            }
+        }
+        perm.copy_from_slice(buffer);
+        width *= 2;
+    }
+    inversions
+}
+
+impl Permutation {
+    /// Number of pairs `i < j` with `self[i] > self[j]`. Has $O(n \log n)$ time complexity.
+    pub fn inversions(&self) -> usize {
+        let mut perm = self.as_slice().to_vec();
+        let mut buffer = vec![0; perm.len()];
+        sort_counting_inversions(&mut perm, &mut buffer)
+    }
+
+    /// `true` if the permutation is a product of an even number of transpositions. Has $O(n)$ time complexity.
+    pub fn is_even(&self) -> bool {
+        (self.get_n() - self.cycles().len()).is_multiple_of(2)
+    }
+
+    /// `1` for even permutations and `-1` for odd ones.
+    pub fn sign(&self) -> i8 {
+        if self.is_even() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Positions `i` with `self[i] > self[i + 1]`.
+    pub fn descents(&self) -> Vec<usize> {
+        let perm = self.as_slice();
+        (1..perm.len())
+            .filter(|&i| perm[i - 1] > perm[i])
+            .map(|i| i - 1)
+            .collect()
+    }
+
+    /// Sum of the descent positions, counted from 1 as is customary.
+    pub fn major_index(&self) -> usize {
+        self.descents().iter().map(|&i| i + 1).sum()
+    }
+
+    /// Number of positions `i` with `self[i] == i`.
+    pub fn fixed_points(&self) -> usize {
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .filter(|&(ii, &i)| ii == i)
+            .count()
+    }
+
+    /// Smallest `k > 0` with ``pow(k)`` equal to the identity, i.e. the lcm of the cycle lengths.
+    /// Returns `None` if it does not fit in ``u128``, which needs n in the thousands.
+    pub fn order(&self) -> Option<u128> {
+        self.cycle_type()
+            .into_iter()
+            .try_fold(1u128, |order, length| {
+                let length = length as u128;
+                (order / gcd(order, length)).checked_mul(length)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Permutation, Permutations};
+
+    /// Checks statistics against their definitions for all permutations of 6
+    #[test]
+    fn statistics_match_definitions() {
+        for perm in Permutations::of(6) {
+            let value = Permutation::new(perm.clone()).unwrap();
+            let inversions = (0..6)
+                .flat_map(|i| (i + 1..6).map(move |j| (i, j)))
+                .filter(|&(i, j)| perm[i] > perm[j])
+                .count();
+            assert_eq!(value.inversions(), inversions);
+            assert_eq!(value.is_even(), inversions % 2 == 0);
+            let order = (1..)
+                .find(|&k| value.pow(k) == Permutation::identity(6))
+                .unwrap();
+            assert_eq!(value.order(), Some(order as u128));
+            assert_eq!(
+                value.fixed_points(),
+                (0..6).filter(|&i| perm[i] == i).count()
+            );
+        }
+        let perm = Permutation::new(vec![3, 1, 2, 0, 5, 4]).unwrap();
+        assert_eq!(perm.descents(), [0, 2, 4]);
+        assert_eq!(perm.major_index(), 9);
+        assert_eq!(perm.sign(), 1);
+        assert_eq!(Permutation::identity(0).order(), Some(1));
+    }
+}
@@ -0,0 +1,115 @@
+//! Subsets of `0..n` in binary reflected Gray code order.
+
+/// Implements ``Iterator`` over all subsets of `0..n`, starting from the empty set, as ascending ``Vec``s of elements.
+///
+/// Consecutive subsets differ by adding or removing one element. The subset is kept as a bitset of ``u64`` words,
+/// so n is not limited to 64, and the element to flip is found with focus pointers (Knuth's Algorithm L),
+/// so each step has $O(1)$ worst-case time complexity.
+pub struct GraySubsets {
+    n: usize,
+    /// Bit `i % 64` of word `i / 64` is set if `i` is in the subset.
+    bits: Vec<u64>,
+    /// Focus pointers; `focus[0]` is the element to flip on the next step.
+    focus: Vec<usize>,
+    is_initiated: bool,
+    is_finished: bool,
+}
+
+impl GraySubsets {
+    /// n must be greater than 0!
+    pub fn of(n: usize) -> GraySubsets {
+        assert!(n > 0);
+        GraySubsets {
+            n,
+            bits: vec![0; n.div_ceil(64)],
+            focus: (0..=n).collect(),
+            is_initiated: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.n
+    }
+
+    /// `true` if `i` is in the current subset.
+    pub fn contains(&self, i: usize) -> bool {
+        self.bits[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// Streaming alternative to ``next()``: borrows the bitset words instead of listing the elements.
+    pub fn next_ref(&mut self) -> Option<&[u64]> {
+        if self.is_initiated {
+            self.next_flip()?;
+        }
+        self.is_initiated = true;
+        Some(&self.bits)
+    }
+
+    /// Steps to the next subset and returns the element that was added or removed.
+    /// The empty set counts as already visited, so the first call performs the first flip.
+    pub fn next_flip(&mut self) -> Option<usize> {
+        self.is_initiated = true;
+        if self.is_finished {
+            return None;
+        }
+        let j = self.focus[0];
+        self.focus[0] = 0;
+        if j == self.n {
+            self.is_finished = true;
+            return None;
+        }
+        self.focus[j] = self.focus[j + 1];
+        self.focus[j + 1] = j + 1;
+        self.bits[j / 64] ^= 1 << (j % 64);
+        Some(j)
+    }
+}
+
+impl Iterator for GraySubsets {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref()?;
+        Some((0..self.n).filter(|&i| self.contains(i)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GraySubsets;
+
+    /// Checks against the closed form `i ^ (i >> 1)` of the Gray code
+    #[test]
+    fn matches_gray_code() {
+        for n in 1..=10 {
+            let mut subsets = GraySubsets::of(n);
+            for i in 0u64..1 << n {
+                assert_eq!(subsets.next_ref(), Some(&[i ^ (i >> 1)][..]));
+            }
+            assert_eq!(subsets.next_ref(), None);
+        }
+        let mut subsets = GraySubsets::of(3);
+        let flips: Vec<usize> = std::iter::from_fn(|| subsets.next_flip()).collect();
+        assert_eq!(flips, [0, 1, 0, 2, 0, 1, 0]);
+    }
+
+    /// Checks flips and element lists past 64 elements
+    #[test]
+    fn large_sets() {
+        let mut subsets = GraySubsets::of(130);
+        assert_eq!(subsets.next(), Some(vec![]));
+        let mut current = [false; 130];
+        for _ in 0..1000 {
+            let i = subsets.next_flip().unwrap();
+            current[i] = !current[i];
+            assert!((0..130).all(|i| subsets.contains(i) == current[i]));
+        }
+        assert_eq!(subsets.next_ref().map(<[u64]>::len), Some(3));
+        let mut small = GraySubsets::of(2);
+        assert_eq!(
+            small.by_ref().collect::<Vec<_>>(),
+            [vec![], vec![0], vec![0, 1], vec![1]]
+        );
+    }
+}